@@ -0,0 +1,41 @@
+#![no_main]
+use eth_checksum::{Address, Checksum, ParseOptions, Verification};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let checksummed = Checksum::from_str(input);
+
+    if let Ok(checksummed) = &checksummed {
+        assert_eq!(input.len(), checksummed.len());
+        assert!(input.eq_ignore_ascii_case(checksummed));
+        assert!(matches!(
+            Checksum::verify(checksummed),
+            Ok(Verification::Valid) | Ok(Verification::Lowercase) | Ok(Verification::Uppercase)
+        ));
+    }
+
+    assert_eq!(checksummed.is_ok(), Checksum::verify(input).is_ok());
+    assert_eq!(checksummed.is_ok(), Checksum::encode(input).is_ok());
+    assert_eq!(checksummed.is_ok(), Address::parse_lenient(input).is_ok());
+
+    assert_eq!(
+        Checksum::validate(input).is_ok(),
+        Checksum::diagnose(input).is_empty()
+    );
+    if let Ok(corrections) = Checksum::suggest_corrections(input) {
+        for correction in corrections {
+            assert!(Checksum::validate(&correction.address).is_ok());
+            assert!(correction.distance > 0 && !correction.positions.is_empty());
+        }
+    }
+    let _ = Address::parse(input);
+    let _ = Checksum::with_chain_id(30).from_str(input);
+    let _ = Checksum::with_options(ParseOptions::lenient()).diagnose(input);
+
+    let normalizing = Checksum::with_options(ParseOptions::new().normalize_unicode(true));
+    assert_eq!(
+        normalizing.validate(input).is_ok(),
+        normalizing.diagnose(input).is_empty()
+    );
+    let _ = normalizing.suggest_corrections(input);
+});
@@ -0,0 +1,34 @@
+#![no_main]
+use eth_checksum::{Address, TryChecksum};
+use libfuzzer_sys::fuzz_target;
+use std::{borrow::Cow, convert::TryFrom};
+
+fn exercise<T: TryChecksum + ?Sized>(input: &T) {
+    let _ = input.try_checksum();
+    let _ = input.try_verify();
+}
+
+fuzz_target!(|data: &[u8]| {
+    exercise(data);
+    exercise(&data.to_vec());
+
+    if let Ok(bytes) = <[u8; 20]>::try_from(data) {
+        exercise(&bytes);
+        exercise(&Address::from(bytes));
+    }
+    if let Ok(bytes) = <[u8; 40]>::try_from(data) {
+        exercise(&bytes);
+    }
+    if let Ok(bytes) = <[u8; 42]>::try_from(data) {
+        exercise(&bytes);
+    }
+
+    if let Ok(string) = std::str::from_utf8(data) {
+        exercise(string);
+        exercise(&string.to_string());
+        exercise(&Box::<str>::from(string));
+        exercise(&Cow::Borrowed(string));
+    }
+
+    let _ = Address::try_from(data);
+});
@@ -0,0 +1,687 @@
+use super::*;
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+use core::{convert::TryFrom, fmt};
+
+/// A 20-byte Ethereum address.
+///
+/// Ordering (and hashing) is by the raw bytes, which matches the ordering of
+/// the address as a Solidity `uint160`.
+/// Comparing with a `str` is case-insensitive and accepts an optional `0x` prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address([u8; 20]);
+
+/// The Keccak-256 hash of [`Address::CREATE3_PROXY_BYTECODE`]
+const CREATE3_PROXY_BYTECODE_HASH: [u8; 32] = [
+    0x21, 0xc3, 0x5d, 0xbe, 0x1b, 0x34, 0x4a, 0x24, 0x88, 0xcf, 0x33, 0x21, 0xd6, 0xce, 0x54, 0x2f,
+    0x8e, 0x9f, 0x30, 0x55, 0x44, 0xff, 0x09, 0xe4, 0x99, 0x3a, 0x62, 0x31, 0x9a, 0x49, 0x7c, 0x1f,
+];
+
+impl Address {
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// The address of an uncompressed secp256k1 public key, i.e. the last 20 bytes of the
+    /// Keccak-256 hash of its 64 bytes point, optionally prefixed with `0x04` (65 bytes).
+    ///
+    /// With the `k256` feature the compressed (33 bytes) public keys are decompressed first.
+    ///
+    /// ```
+    /// use eth_checksum::Address;
+    ///
+    /// // the generator point, i.e. the public key of the private key `1`
+    /// let mut public_key = [0x04; 65];
+    /// public_key[1..].copy_from_slice(&[
+    ///     0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+    ///     0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b,
+    ///     0x16, 0xf8, 0x17, 0x98, 0x48, 0x3a, 0xda, 0x77, 0x26, 0xa3, 0xc4, 0x65, 0x5d, 0xa4,
+    ///     0xfb, 0xfc, 0x0e, 0x11, 0x08, 0xa8, 0xfd, 0x17, 0xb4, 0x48, 0xa6, 0x85, 0x54, 0x19,
+    ///     0x9c, 0x47, 0xd0, 0x8f, 0xfb, 0x10, 0xd4, 0xb8,
+    /// ]);
+    ///
+    /// let address = Address::from_public_key(&public_key).unwrap();
+    /// assert_eq!(
+    ///     "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+    ///     &*address.checksummed()
+    /// );
+    /// assert_eq!(Ok(address), Address::from_public_key(&public_key[1..]));
+    /// ```
+    pub fn from_public_key(public_key: &[u8]) -> Result<Self, KeyError> {
+        let point = match public_key {
+            #[cfg(feature = "k256")]
+            [0x02 | 0x03, x @ ..] if x.len() == 32 => {
+                return k256::PublicKey::from_sec1_bytes(public_key)
+                    .map(Self::from)
+                    .map_err(|_| KeyError::PublicKeyPoint)
+            }
+            [0x04, point @ ..] if point.len() == 64 => point,
+            [prefix, point @ ..] if point.len() == 64 => {
+                return Err(KeyError::PublicKeyPrefix { actual: *prefix })
+            }
+            point if point.len() == 64 => point,
+            _ => {
+                return Err(KeyError::PublicKeyLength {
+                    actual: public_key.len(),
+                })
+            }
+        };
+
+        Ok(Self::from_hash(&keccak256(point)))
+    }
+
+    /// The address of a secp256k1 private key, enabled with the `k256` feature
+    #[cfg(feature = "k256")]
+    pub fn from_private_key(private_key: &[u8; 32]) -> Result<Self, KeyError> {
+        k256::SecretKey::from_bytes(&(*private_key).into())
+            .map(|secret_key| Self::from(secret_key.public_key()))
+            .map_err(|_| KeyError::PrivateKey)
+    }
+
+    /// The address of the contract created by the `sender` with its `nonce`, i.e. with the
+    /// `CREATE` opcode or a contract creation transaction.
+    ///
+    /// It's the last 20 bytes of the Keccak-256 hash of the RLP encoded `[sender, nonce]`.
+    ///
+    /// ```
+    /// use eth_checksum::Address;
+    ///
+    /// let sender = Address::parse("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+    /// assert_eq!(
+    ///     "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d",
+    ///     Address::create(&sender, 0).to_string()
+    /// );
+    /// ```
+    pub fn create(sender: &Address, nonce: u64) -> Self {
+        // the list prefix, the 21 bytes of the sender and at most 9 bytes of the nonce
+        let mut rlp = [0_u8; 31];
+        rlp[1] = 0x80 + 20;
+        rlp[2..22].copy_from_slice(&sender.0);
+
+        // the big-endian nonce without its leading zeros, and `0x80`, the empty string, for 0
+        let nonce_bytes = nonce.to_be_bytes();
+        let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+        let len = match nonce_bytes {
+            [byte] if *byte < 0x80 => {
+                rlp[22] = *byte;
+                23
+            }
+            _ => {
+                rlp[22] = 0x80 + nonce_bytes.len() as u8;
+                rlp[23..23 + nonce_bytes.len()].copy_from_slice(nonce_bytes);
+                23 + nonce_bytes.len()
+            }
+        };
+        // a short list, of less than 56 bytes
+        rlp[0] = 0xc0 + (len - 1) as u8;
+
+        Self::from_hash(&keccak256(&rlp[..len]))
+    }
+
+    /// The address of the contract created with the `CREATE2` opcode (EIP-1014) by the `deployer`,
+    /// with the `salt` and the Keccak-256 hash of the init code.
+    ///
+    /// It's the last 20 bytes of the Keccak-256 hash of `0xff ‖ deployer ‖ salt ‖ init_code_hash`.
+    pub fn create2(deployer: &Address, salt: [u8; 32], init_code_hash: [u8; 32]) -> Self {
+        let mut bytes = [0_u8; 85];
+        bytes[0] = 0xff;
+        bytes[1..21].copy_from_slice(&deployer.0);
+        bytes[21..53].copy_from_slice(&salt);
+        bytes[53..].copy_from_slice(&init_code_hash);
+
+        Self::from_hash(&keccak256(&bytes))
+    }
+
+    /// Same as [`Address::create2`], but hashing the init code
+    ///
+    /// ```
+    /// use eth_checksum::Address;
+    ///
+    /// let deployer = Address::parse("0x00000000000000000000000000000000deadbeef").unwrap();
+    /// let mut salt = [0_u8; 32];
+    /// salt[28..].copy_from_slice(&[0xca, 0xfe, 0xba, 0xbe]);
+    ///
+    /// assert_eq!(
+    ///     "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+    ///     Address::create2_from_init_code(&deployer, salt, &[0xde, 0xad, 0xbe, 0xef]).to_string()
+    /// );
+    /// ```
+    pub fn create2_from_init_code(deployer: &Address, salt: [u8; 32], init_code: &[u8]) -> Self {
+        Self::create2(deployer, salt, keccak256(init_code))
+    }
+
+    /// The address of the contract created with `CREATE3` by the `deployer`, with the `salt`.
+    ///
+    /// `CREATE3` deploys the [`CREATE3_PROXY_BYTECODE`](Address::CREATE3_PROXY_BYTECODE) with
+    /// `CREATE2`, which deploys the contract with `CREATE` and its first nonce, so the address
+    /// doesn't depend on the init code of the contract.
+    pub fn create3(deployer: &Address, salt: [u8; 32]) -> Self {
+        let proxy = Self::create2(deployer, salt, CREATE3_PROXY_BYTECODE_HASH);
+
+        Self::create(&proxy, 1)
+    }
+
+    /// The init code of the standard `CREATE3` proxy, which deploys its calldata with `CREATE`
+    pub const CREATE3_PROXY_BYTECODE: [u8; 16] = [
+        0x67, 0x36, 0x3d, 0x3d, 0x37, 0x36, 0x3d, 0x34, 0xf0, 0x3d, 0x52, 0x60, 0x08, 0x60, 0x18,
+        0xf3,
+    ];
+
+    /// The last 20 bytes of the hash
+    pub(crate) fn from_hash(hash: &[u8; 32]) -> Self {
+        let mut bytes = [0_u8; 20];
+        bytes.copy_from_slice(&hash[12..]);
+
+        Self(bytes)
+    }
+
+    /// Parses a (prefixed or not) address, rejecting mixed-case addresses with an invalid checksum.
+    ///
+    /// All lowercase and all uppercase addresses carry no checksum and are accepted.
+    pub fn parse(input: &str) -> Result<Self, Error<'_>> {
+        let (prefix, address) = split_prefix(input)?;
+
+        if let (_, Verification::Invalid(positions)) =
+            verify_checksum::<DefaultKeccak>(address, None)
+                .map_err(|error| error.after_prefix(prefix))?
+        {
+            return Err(Error::Checksum { positions });
+        }
+
+        decode_hex(address).map_err(|error| error.after_prefix(prefix))
+    }
+
+    /// Parses a (prefixed or not) address regardless of its casing.
+    pub fn parse_lenient(input: &str) -> Result<Self, Error<'_>> {
+        let (prefix, address) = split_prefix(input)?;
+
+        decode_hex(address).map_err(|error| error.after_prefix(prefix))
+    }
+
+    /// Writes the EIP-55 checksummed and prefixed address into the buffer without allocating
+    pub fn encode_to_slice<'b>(&self, buffer: &'b mut [u8; 42]) -> &'b str {
+        buffer.copy_from_slice(self.checksummed().as_bytes());
+
+        ascii_str(buffer)
+    }
+
+    /// Writes the 40 lowercase hex characters, without the prefix
+    pub(crate) fn write_lowercase_hex(&self, hex: &mut [u8; 40]) {
+        for (byte, chunk) in self.0.iter().zip(hex.chunks_exact_mut(2)) {
+            chunk[0] = HEX_CHARS[(byte >> 4) as usize];
+            chunk[1] = HEX_CHARS[(byte & 0x0f) as usize];
+        }
+    }
+
+    /// The EIP-55 checksummed and prefixed address, stored inline
+    pub fn checksummed(&self) -> ChecksummedAddress {
+        <Checksummer>::default().encode_address(self)
+    }
+
+    /// Writes the EIP-55 checksummed and prefixed address without allocating
+    pub fn write_checksum<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str(self.encode_to_slice(&mut [0_u8; 42]))
+    }
+
+    /// Same as [`Address::write_checksum`], but for an [`std::io::Write`]
+    #[cfg(feature = "std")]
+    pub fn write_checksum_io<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.encode_to_slice(&mut [0_u8; 42]).as_bytes())
+    }
+
+    fn eq_str(&self, other: &str) -> bool {
+        Self::parse_lenient(other).is_ok_and(|address| &address == self)
+    }
+}
+
+/// The lowercase hex characters, by nibble value
+pub(crate) const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+fn decode_hex(address: &str) -> Result<Address, Error<'_>> {
+    let mut bytes = [0_u8; 20];
+
+    for (i, byte) in address.bytes().enumerate() {
+        let nibble = match byte {
+            b'0'..=b'9' => byte - b'0',
+            b'a'..=b'f' => byte - b'a' + 10,
+            b'A'..=b'F' => byte - b'A' + 10,
+            _ => return Err(invalid_hex_char(address, i)),
+        };
+
+        bytes[i / 2] |= if i & 1 == 0 { nibble << 4 } else { nibble };
+    }
+
+    Ok(Address(bytes))
+}
+
+/// Strict parsing, see [`Address::parse`]
+#[cfg(feature = "alloc")]
+impl FromStr for Address {
+    type Err = OwnedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(Error::into_owned)
+    }
+}
+
+/// Checksummed and prefixed address
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_checksum(f)
+    }
+}
+
+impl fmt::LowerHex for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(PREFIX)?;
+        }
+
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl fmt::UpperHex for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(PREFIX)?;
+        }
+
+        self.0.iter().try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Address> for [u8; 20] {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+#[cfg(feature = "k256")]
+impl From<k256::PublicKey> for Address {
+    fn from(public_key: k256::PublicKey) -> Self {
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let point = public_key.to_encoded_point(false);
+        Self::from_hash(&keccak256(&point.as_bytes()[1..]))
+    }
+}
+
+impl TryFrom<&[u8]> for Address {
+    type Error = Error<'static>;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; 20]>::try_from(bytes)
+            .map(Self)
+            .map_err(|_| Error::ByteLength {
+                expected: 20,
+                actual: bytes.len(),
+            })
+    }
+}
+
+impl PartialEq<str> for Address {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for Address {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<Address> for str {
+    fn eq(&self, other: &Address) -> bool {
+        other.eq_str(self)
+    }
+}
+
+impl PartialEq<Address> for &str {
+    fn eq(&self, other: &Address) -> bool {
+        other.eq_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{hex_bytes, CHECKSUMMED};
+
+    #[test]
+    fn test_address_from_str_and_display() {
+        let address = Address::parse(CHECKSUMMED).expect("Should be valid address");
+
+        assert_eq!(CHECKSUMMED, address.to_string());
+        assert_eq!(
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            format!("{:x}", address)
+        );
+        assert_eq!(
+            "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+            format!("{:#X}", address)
+        );
+
+        // `Checksum::from_str` is the lenient parsing followed by `Display`
+        #[cfg(feature = "alloc")]
+        let lowercase = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            Checksum::from_str(lowercase),
+            Address::parse_lenient(lowercase).map(|address| address.to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_address_strict_and_lenient_parsing() {
+        let invalid = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+
+        match Address::parse(invalid) {
+            Err(Error::Checksum { positions }) => assert_eq!(2, positions.len()),
+            other => panic!("Expected checksum error, got {:?}", other),
+        }
+
+        assert_eq!(
+            "invalid_checksum",
+            invalid.parse::<Address>().unwrap_err().code()
+        );
+
+        let address = Address::parse_lenient(invalid).expect("Casing is ignored");
+        assert_eq!(CHECKSUMMED, address.to_string());
+
+        let prefix_err = "0X5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse::<Address>()
+            .unwrap_err();
+        assert_eq!(
+            Error::Prefix {
+                expected: PREFIX,
+                actual: "0X",
+            },
+            prefix_err.error()
+        );
+    }
+
+    #[test]
+    fn test_address_bytes_and_equality() {
+        let address = Address::parse(CHECKSUMMED).expect("Should be valid address");
+
+        assert_eq!(address, Address::try_from(address.as_ref()).unwrap());
+        assert_eq!(
+            Err(Error::ByteLength {
+                expected: 20,
+                actual: 3,
+            }),
+            Address::try_from(&[1_u8, 2, 3][..])
+        );
+
+        assert_eq!(address, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        assert_eq!("5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED", address);
+        assert_ne!(address, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaee");
+
+        let mut lower = [0_u8; 20];
+        lower[19] = 0xff;
+        let mut higher = [0_u8; 20];
+        higher[0] = 0x01;
+        assert!(Address::from(lower) < Address::from(higher));
+    }
+
+    #[test]
+    fn test_address_from_public_key() {
+        // the public keys of the private keys `1`, `2` and `3`
+        let vectors = [
+            (
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                 483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+                "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+            ),
+            (
+                "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5\
+                 1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+                "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF",
+            ),
+            (
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9\
+                 388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+                "0x6813Eb9362372EEF6200f3b1dbC3f819671cBA69",
+            ),
+        ];
+
+        for (point, expected) in vectors.iter() {
+            let point = hex_bytes(point);
+            let address = Address::from_public_key(&point).expect("Should be a public key");
+            assert_eq!(*expected, address.to_string());
+
+            let prefixed = [&[0x04], &point[..]].concat();
+            assert_eq!(Ok(address), Address::from_public_key(&prefixed));
+        }
+    }
+
+    #[test]
+    fn test_address_from_invalid_public_key() {
+        let compressed =
+            hex_bytes("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        #[cfg(not(feature = "k256"))]
+        assert_eq!(
+            Err(KeyError::PublicKeyLength { actual: 33 }),
+            Address::from_public_key(&compressed)
+        );
+        #[cfg(feature = "k256")]
+        assert_eq!(
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+            Address::from_public_key(&compressed).unwrap().to_string()
+        );
+
+        let mut hybrid = [0_u8; 65];
+        hybrid[0] = 0x06;
+        let error = Address::from_public_key(&hybrid).unwrap_err();
+        assert_eq!(KeyError::PublicKeyPrefix { actual: 0x06 }, error);
+        assert_eq!("invalid_public_key_prefix", error.code());
+        assert_eq!(
+            "invalid public key prefix `0x06`, expected `0x04` (uncompressed)",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_address_create() {
+        let sender = Address::parse("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+        let created = [
+            "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d",
+            "0x343c43A37D37dfF08AE8C4A11544c718AbB4fCF8",
+            "0xf778B86FA74E846c4f0a1fBd1335FE81c00a0C91",
+            "0xffFd933A0bC612844eaF0C6Fe3E5b8E9B6C1d19c",
+        ];
+        for (nonce, created) in created.iter().enumerate() {
+            assert_eq!(*created, Address::create(&sender, nonce as u64).to_string());
+        }
+
+        // mainnet deployments: Tether USD, Uniswap V2 Factory and WETH9
+        let deployments = [
+            (
+                "0x36928500Bc1dCd7af6a2B4008875CC336b927D57",
+                6,
+                "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+            ),
+            (
+                "0x9C33eaCc2F50E39940D3AfaF2c7B8246B681A374",
+                0,
+                "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+            ),
+            (
+                "0x4F26FfBe5F04ED43630fdC30A87638d53D0b0876",
+                446,
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            ),
+        ];
+        for (deployer, nonce, created) in deployments.iter() {
+            let deployer = Address::parse(deployer).unwrap();
+            assert_eq!(*created, Address::create(&deployer, *nonce).to_string());
+        }
+
+        // the single byte and long integer encodings of the nonce
+        let rlp = |nonce: &str| {
+            let nonce = hex_bytes(nonce);
+            let mut rlp = vec![0xc0 + 21 + nonce.len() as u8, 0x94];
+            rlp.extend_from_slice(sender.as_bytes());
+            rlp.extend_from_slice(&nonce);
+
+            Address::from_hash(&keccak256(&rlp))
+        };
+        let nonces = [
+            (0x7f, "7f"),
+            (0x80, "8180"),
+            (0xff, "81ff"),
+            (0x0100, "820100"),
+            (0x0001_0000, "83010000"),
+            (u64::MAX, "88ffffffffffffffff"),
+        ];
+        for (nonce, encoded) in nonces.iter() {
+            assert_eq!(rlp(encoded), Address::create(&sender, *nonce), "{}", nonce);
+        }
+    }
+
+    #[test]
+    fn test_address_create2() {
+        // the EIP-1014 examples
+        let deadbeef = "deadbeef".repeat(11);
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000",
+                "00",
+                "00",
+                "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "00",
+                "00",
+                "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00",
+                "0xD04116cDd17beBE565EB2422F2497E06cC1C9833",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                "00",
+                "deadbeef",
+                "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                &deadbeef,
+                "0x1d8bfDC5D46DC4f61D6b6115972536eBE6A8854C",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                "00",
+                "",
+                "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ];
+
+        for (deployer, salt, init_code, created) in vectors.iter() {
+            let deployer = Address::parse(deployer).unwrap();
+            let mut salt_bytes = [0_u8; 32];
+            let salt = hex_bytes(salt);
+            salt_bytes[32 - salt.len()..].copy_from_slice(&salt);
+            let init_code = hex_bytes(init_code);
+
+            let address = Address::create2_from_init_code(&deployer, salt_bytes, &init_code);
+            assert_eq!(*created, address.to_string());
+            assert_eq!(
+                address,
+                Address::create2(&deployer, salt_bytes, keccak256(&init_code))
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_create3() {
+        assert_eq!(
+            CREATE3_PROXY_BYTECODE_HASH,
+            keccak256(&Address::CREATE3_PROXY_BYTECODE)
+        );
+
+        let deployer = Address::parse("0x00000000000000000000000000000000deadbeef").unwrap();
+        let salt = [0x42_u8; 32];
+
+        // the formula of solady's `CREATE3.predictDeterministicAddress(salt, deployer)`,
+        // computed outside of this crate
+        assert_eq!(
+            "0x72bB8069e74799A8816C8Dc715a76C5578F88E07",
+            Address::create3(&deployer, salt).to_string()
+        );
+        assert_eq!(
+            "0xaf866bF37d07748b7B47e8B57f2de95d6D224bD5",
+            Address::create3(&deployer, [0; 32]).to_string()
+        );
+
+        let proxy =
+            Address::create2_from_init_code(&deployer, salt, &Address::CREATE3_PROXY_BYTECODE);
+        assert_eq!(
+            Address::create(&proxy, 1),
+            Address::create3(&deployer, salt)
+        );
+    }
+
+    #[cfg(feature = "k256")]
+    #[test]
+    fn test_address_from_private_and_compressed_keys() {
+        let mut private_key = [0_u8; 32];
+        private_key[31] = 2;
+        let address = Address::from_private_key(&private_key).expect("Should be a private key");
+        assert_eq!(
+            "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF",
+            address.to_string()
+        );
+
+        // the even `y` of `2G`
+        let compressed =
+            hex_bytes("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5");
+        assert_eq!(Ok(address), Address::from_public_key(&compressed));
+
+        let mut not_a_point = [0xff_u8; 33];
+        not_a_point[0] = 0x03;
+        assert_eq!(
+            Err(KeyError::PublicKeyPoint),
+            Address::from_public_key(&not_a_point)
+        );
+
+        // zero and the curve order
+        let order = hex_bytes("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        for invalid in [[0_u8; 32], <[u8; 32]>::try_from(&order[..]).unwrap()].iter() {
+            assert_eq!(
+                Err(KeyError::PrivateKey),
+                Address::from_private_key(invalid)
+            );
+        }
+    }
+}
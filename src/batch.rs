@@ -0,0 +1,227 @@
+//! Checksumming many addresses at once.
+//!
+//! The results are [`ChecksummedAddress`]es stored inline, so a batch only allocates its output.
+//! With the `rayon` feature the batches are split across the available cores.
+use super::*;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The minimum number of addresses checksummed by a single `rayon` task
+#[cfg(feature = "rayon")]
+const MIN_TASK_LEN: usize = 1024;
+
+impl Checksum {
+    /// Checksums every (prefixed or not) address, see [`Checksummer::checksum_batch`].
+    #[cfg(feature = "alloc")]
+    pub fn checksum_batch<'a>(inputs: &[&'a str]) -> Vec<Result<ChecksummedAddress, Error<'a>>> {
+        <Checksummer>::default().checksum_batch(inputs)
+    }
+}
+
+impl<H: Keccak256> Checksummer<H> {
+    /// Checksums every (prefixed or not) address.
+    ///
+    /// The results are in the order of the inputs, i.e. the error of an invalid input is at its index.
+    ///
+    /// ```
+    /// use eth_checksum::Checksum;
+    ///
+    /// let results = Checksum::checksum_batch(&[
+    ///     "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+    ///     "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beae",
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    ///     results[0].as_deref().unwrap()
+    /// );
+    /// assert!(results[1].is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn checksum_batch<'a>(
+        &self,
+        inputs: &[&'a str],
+    ) -> Vec<Result<ChecksummedAddress, Error<'a>>> {
+        let mut results = Vec::new();
+        self.checksum_batch_into(inputs, &mut results);
+
+        results
+    }
+
+    /// Same as [`Checksummer::checksum_batch`], but reuses the `results` (clearing them first),
+    /// so consecutive batches don't reallocate.
+    #[cfg(feature = "alloc")]
+    pub fn checksum_batch_into<'a>(
+        &self,
+        inputs: &[&'a str],
+        results: &mut Vec<Result<ChecksummedAddress, Error<'a>>>,
+    ) {
+        results.clear();
+        results.reserve(inputs.len());
+
+        #[cfg(feature = "rayon")]
+        results.par_extend(
+            inputs
+                .par_iter()
+                .with_min_len(MIN_TASK_LEN)
+                .map(|input| self.encode(input)),
+        );
+
+        #[cfg(not(feature = "rayon"))]
+        results.extend(inputs.iter().map(|input| self.encode(input)));
+    }
+}
+
+/// Checksums the addresses of an iterator lazily, see [`ChecksumIterator::checksummed`].
+pub struct Checksummed<I, H = DefaultKeccak> {
+    inputs: I,
+    checksummer: Checksummer<H>,
+}
+
+impl<I: Clone, H> Clone for Checksummed<I, H> {
+    fn clone(&self) -> Self {
+        Self {
+            inputs: self.inputs.clone(),
+            checksummer: self.checksummer,
+        }
+    }
+}
+
+impl<I: fmt::Debug, H> fmt::Debug for Checksummed<I, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checksummed")
+            .field("inputs", &self.inputs)
+            .field("checksummer", &self.checksummer)
+            .finish()
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a str>, H: Keccak256> Iterator for Checksummed<I, H> {
+    type Item = Result<ChecksummedAddress, Error<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inputs
+            .next()
+            .map(|input| self.checksummer.encode(input))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inputs.size_hint()
+    }
+}
+
+/// An iterator adapter checksumming the addresses as they are iterated, without allocating.
+///
+/// ```
+/// use eth_checksum::ChecksumIterator;
+///
+/// let csv = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\n0xq";
+///
+/// let results = csv.lines().checksummed().collect::<Vec<_>>();
+/// assert_eq!(
+///     "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+///     results[0].as_deref().unwrap()
+/// );
+/// assert!(results[1].is_err());
+/// ```
+pub trait ChecksumIterator<'a>: Iterator<Item = &'a str> + Sized {
+    fn checksummed(self) -> Checksummed<Self> {
+        self.checksummed_with(Checksummer::default())
+    }
+
+    /// Checksums with the given checksummer, e.g. for an EIP-1191 chain id
+    fn checksummed_with<H: Keccak256>(self, checksummer: Checksummer<H>) -> Checksummed<Self, H> {
+        Checksummed {
+            inputs: self,
+            checksummer,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a str>> ChecksumIterator<'a> for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::tests::CHECKSUMMED;
+
+    const LOWERCASE: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_checksum_batch_keeps_errors_aligned() {
+        // more than a single `rayon` task
+        let inputs = (0..5000)
+            .map(|i| match i % 3 {
+                0 => LOWERCASE,
+                1 => "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeq",
+                _ => "0x",
+            })
+            .collect::<Vec<_>>();
+
+        let results = Checksum::checksum_batch(&inputs);
+        assert_eq!(inputs.len(), results.len());
+
+        for (i, result) in results.iter().enumerate() {
+            match (i % 3, result) {
+                (0, Ok(checksummed)) => assert_eq!(*checksummed, CHECKSUMMED),
+                (1, Err(Error::HexChar { index: 41, .. })) => {}
+                (2, Err(Error::Length { actual: 2, .. })) => {}
+                (_, other) => panic!("Unexpected result at index {}: {:?}", i, other),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_checksum_batch_into_reuses_results() {
+        let checksummer = Checksum::with_chain_id(30);
+        let mut results = Vec::new();
+
+        checksummer.checksum_batch_into(&[LOWERCASE; 100], &mut results);
+        let capacity = results.capacity();
+
+        checksummer.checksum_batch_into(&[LOWERCASE, "0x"], &mut results);
+        assert_eq!(2, results.len());
+        assert_eq!(capacity, results.capacity());
+        assert_eq!(
+            Ok(checksummer
+                .encode(LOWERCASE)
+                .expect("Should be valid address")),
+            results[0]
+        );
+    }
+
+    #[test]
+    fn test_checksummed_iterator() {
+        let inputs = [LOWERCASE, "0x"];
+
+        let eip_55 = inputs.iter().copied().checksummed().collect::<Vec<_>>();
+        assert_eq!(
+            inputs
+                .iter()
+                .map(|input| Checksum::encode(input))
+                .collect::<Vec<_>>(),
+            eip_55
+        );
+
+        let eip_1191 = inputs
+            .iter()
+            .copied()
+            .checksummed_with(Checksum::with_chain_id(30))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            inputs
+                .iter()
+                .map(|input| Checksum::with_chain_id(30).encode(input))
+                .collect::<Vec<_>>(),
+            eip_1191
+        );
+
+        #[cfg(feature = "alloc")]
+        assert_eq!(Checksum::checksum_batch(&inputs), eip_55);
+    }
+}
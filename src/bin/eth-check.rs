@@ -0,0 +1,541 @@
+//! `eth-check` - checksum and verify Ethereum addresses from the command line.
+//!
+//! Addresses are taken from the arguments or, when none are given, from stdin (one per line).
+use clap::{Parser, Subcommand, ValueEnum};
+use eth_checksum::{
+    Address, Checksum, Checksummer, Error, ParseOptions, TryChecksum, Verification,
+};
+use serde::Serialize;
+use std::{
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "eth-check",
+    version,
+    about = "Checksum and verify Ethereum addresses"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Normalizes the addresses to their checksummed form, regardless of their casing
+    Checksum(Args),
+    /// Verifies the checksum of the addresses, failing on any invalid or mis-cased address
+    Verify {
+        #[command(flatten)]
+        args: Args,
+        /// Fail on all lowercase or all uppercase addresses, which carry no checksum
+        #[arg(long)]
+        require_checksum: bool,
+    },
+    /// Computes the checksummed addresses of the contracts created by the sender with the nonces
+    Create {
+        /// The address of the sender (an account or, for the `CREATE` opcode, a contract)
+        sender: String,
+        /// The nonces of the sender
+        #[arg(required = true)]
+        nonces: Vec<u64>,
+    },
+}
+
+#[derive(clap::Args)]
+struct Args {
+    /// The addresses, read from stdin (one per line) when none are given
+    addresses: Vec<String>,
+    /// The output format
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+    /// Use EIP-1191 chain specific checksums
+    #[arg(long)]
+    chain_id: Option<u64>,
+    /// Map Unicode look-alikes (e.g. full-width digits) to ASCII and drop invisible characters
+    #[arg(long)]
+    normalize_unicode: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Plain,
+    Json,
+    Ndjson,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Valid,
+    Lowercase,
+    Uppercase,
+    Invalid,
+    Error,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Valid => "valid",
+            Status::Lowercase => "lowercase",
+            Status::Uppercase => "uppercase",
+            Status::Invalid => "invalid",
+            Status::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Record {
+    line: usize,
+    input: String,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorRecord>,
+    /// The caret-pointed error for the plain output
+    #[serde(skip)]
+    rendered: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorRecord {
+    /// The `Error` variant
+    kind: &'static str,
+    /// The stable error code, see `Error::code`
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    char: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    positions: Option<Vec<usize>>,
+}
+
+impl From<Error<'_>> for ErrorRecord {
+    fn from(error: Error<'_>) -> Self {
+        let kind = match error {
+            Error::Length { .. } => "Length",
+            Error::Prefix { .. } => "Prefix",
+            Error::Utf8(_) => "Utf8",
+            Error::HexChar { .. } => "HexChar",
+            Error::Confusable { .. } => "Confusable",
+            Error::Checksum { .. } => "Checksum",
+            Error::MissingChecksum => "MissingChecksum",
+            Error::ByteLength { .. } => "ByteLength",
+        };
+        let (index, char) = match error {
+            Error::HexChar { value, index } | Error::Confusable { value, index } => {
+                (Some(index), Some(value))
+            }
+            _ => (None, None),
+        };
+        let positions = match error {
+            Error::Checksum { positions } => Some(positions.iter().collect()),
+            _ => None,
+        };
+
+        Self {
+            kind,
+            code: error.code(),
+            message: error.to_string(),
+            index,
+            char,
+            positions,
+        }
+    }
+}
+
+/// Checksums the line, an invalid UTF-8 one being an [`Error::Utf8`]
+fn checksum(checksummer: &Checksummer, line: usize, input: &[u8]) -> Record {
+    let text = String::from_utf8_lossy(input);
+    let (status, output, error, rendered) = match input.try_checksum_with(checksummer) {
+        Ok(checksummed) => (Status::Ok, Some(checksummed), None, None),
+        Err(error) => {
+            let rendered = error.clone().with_input(&text).render().to_string();
+            (Status::Error, None, Some(error.into()), Some(rendered))
+        }
+    };
+
+    Record {
+        line,
+        input: text.into_owned(),
+        status,
+        output,
+        error,
+        rendered,
+    }
+}
+
+fn verify(checksummer: &Checksummer, line: usize, input: &[u8]) -> Record {
+    let (status, error) = match input.try_verify_with(checksummer) {
+        Ok(Verification::Valid) => (Status::Valid, None),
+        Ok(Verification::Lowercase) => (Status::Lowercase, None),
+        Ok(Verification::Uppercase) => (Status::Uppercase, None),
+        Ok(Verification::Invalid(positions)) => {
+            (Status::Invalid, Some(Error::Checksum { positions }.into()))
+        }
+        Err(error) => (Status::Error, Some(error.into())),
+    };
+
+    Record {
+        line,
+        input: String::from_utf8_lossy(input).into_owned(),
+        status,
+        output: input.try_checksum_with(checksummer).ok(),
+        error,
+        rendered: None,
+    }
+}
+
+/// Quotes the CSV field if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes the records as they are checksummed, except for the JSON array which is written
+/// at the end
+struct RecordWriter<'c, W> {
+    out: W,
+    format: Format,
+    command: &'c Command,
+    json: Vec<Record>,
+}
+
+impl<'c, W: Write> RecordWriter<'c, W> {
+    fn new(mut out: W, format: Format, command: &'c Command) -> io::Result<Self> {
+        if format == Format::Csv {
+            writeln!(
+                out,
+                "line,input,status,output,error,code,message,index,char"
+            )?;
+        }
+
+        Ok(Self {
+            out,
+            format,
+            command,
+            json: Vec::new(),
+        })
+    }
+
+    fn write(&mut self, record: Record) -> io::Result<()> {
+        let out = &mut self.out;
+
+        match self.format {
+            Format::Plain => {
+                let message = record.error.as_ref().map(|error| error.message.as_str());
+
+                match (self.command, &record.output, message) {
+                    (Command::Verify { .. }, _, Some(message)) => writeln!(
+                        out,
+                        "{}\t{}\t{}",
+                        record.status.as_str(),
+                        record.input,
+                        message
+                    ),
+                    (Command::Verify { .. }, _, None) => {
+                        writeln!(out, "{}\t{}", record.status.as_str(), record.input)
+                    }
+                    (_, Some(output), _) => writeln!(out, "{}", output),
+                    (_, None, _) => write!(
+                        io::stderr(),
+                        "line {}: {}",
+                        record.line,
+                        record.rendered.as_deref().unwrap_or_default()
+                    ),
+                }
+            }
+            Format::Json => {
+                self.json.push(record);
+                Ok(())
+            }
+            Format::Ndjson => {
+                serde_json::to_writer(&mut *out, &record)?;
+                writeln!(out)
+            }
+            Format::Csv => {
+                let error = record.error.as_ref();
+
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{},{}",
+                    record.line,
+                    csv_field(&record.input),
+                    record.status.as_str(),
+                    record.output.as_deref().unwrap_or_default(),
+                    error.map(|error| error.kind).unwrap_or_default(),
+                    error.map(|error| error.code).unwrap_or_default(),
+                    csv_field(
+                        error
+                            .map(|error| error.message.as_str())
+                            .unwrap_or_default()
+                    ),
+                    error
+                        .and_then(|error| error.index)
+                        .map(|index| index.to_string())
+                        .unwrap_or_default(),
+                    csv_field(
+                        &error
+                            .and_then(|error| error.char)
+                            .map(String::from)
+                            .unwrap_or_default()
+                    ),
+                )
+            }
+        }
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            serde_json::to_writer_pretty(&mut self.out, &self.json)?;
+            writeln!(self.out)?;
+        }
+
+        self.out.flush()
+    }
+}
+
+/// Writes the created addresses, one per line
+fn create(mut out: impl Write, sender: &str, nonces: &[u64]) -> io::Result<bool> {
+    let sender = match Address::parse(sender.trim()) {
+        Ok(sender) => sender,
+        Err(error) => {
+            write!(io::stderr(), "{}", error.with_input(sender.trim()).render())?;
+            return Ok(false);
+        }
+    };
+
+    nonces
+        .iter()
+        .try_for_each(|nonce| writeln!(out, "{}", Address::create(&sender, *nonce)))?;
+
+    Ok(true)
+}
+
+fn run(command: Command) -> io::Result<bool> {
+    let (args, require_checksum) = match &command {
+        Command::Checksum(args) => (args, false),
+        Command::Verify {
+            args,
+            require_checksum,
+        } => (args, *require_checksum),
+        Command::Create { sender, nonces } => return create(io::stdout().lock(), sender, nonces),
+    };
+
+    let checksummer = args
+        .chain_id
+        .map(Checksum::with_chain_id)
+        .unwrap_or_default()
+        .with_options(ParseOptions::new().normalize_unicode(args.normalize_unicode));
+
+    let out = RecordWriter::new(io::stdout().lock(), args.format, &command)?;
+    if args.addresses.is_empty() {
+        check_lines(
+            io::stdin().lock().split(b'\n'),
+            out,
+            &checksummer,
+            require_checksum,
+        )
+    } else {
+        let lines = args
+            .addresses
+            .iter()
+            .map(|address| Ok(address.as_bytes().to_vec()));
+        check_lines(lines, out, &checksummer, require_checksum)
+    }
+}
+
+/// Checksums or verifies the lines one by one, writing their records as they go.
+///
+/// The lines are bytes, so that an invalid UTF-8 line is reported like any other invalid address.
+fn check_lines(
+    lines: impl Iterator<Item = io::Result<Vec<u8>>>,
+    mut out: RecordWriter<'_, impl Write>,
+    checksummer: &Checksummer,
+    require_checksum: bool,
+) -> io::Result<bool> {
+    let mut success = true;
+
+    for (i, line) in lines.enumerate() {
+        let line = line?;
+        let input = match std::str::from_utf8(&line) {
+            Ok(input) => input.trim().as_bytes(),
+            Err(_) => line.trim_ascii(),
+        };
+        if input.is_empty() {
+            continue;
+        }
+
+        let record = match out.command {
+            Command::Verify { .. } => verify(checksummer, i + 1, input),
+            _ => checksum(checksummer, i + 1, input),
+        };
+        success &= match record.status {
+            Status::Ok | Status::Valid => true,
+            Status::Lowercase | Status::Uppercase => !require_checksum,
+            Status::Invalid | Status::Error => false,
+        };
+        out.write(record)?;
+    }
+
+    out.finish()?;
+    Ok(success)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("eth-check: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_records() {
+        let checksummer = Checksummer::default();
+
+        let invalid = verify(
+            &checksummer,
+            1,
+            b"0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD",
+        );
+        assert_eq!(Status::Invalid, invalid.status);
+        assert_eq!(
+            Some("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            invalid.output.as_deref()
+        );
+
+        let hex_char = verify(
+            &checksummer,
+            2,
+            b"0xq0fc04fa2d34a66b779fd5cee748268032a146c0",
+        );
+        let json = serde_json::to_value(&hex_char).expect("Should serialize");
+        assert_eq!("error", json["status"]);
+        assert_eq!("HexChar", json["error"]["kind"]);
+        assert_eq!(2, json["error"]["index"]);
+        assert_eq!("q", json["error"]["char"]);
+    }
+
+    #[test]
+    fn test_create_output() {
+        let mut out = Vec::new();
+        let created = create(
+            &mut out,
+            "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0",
+            &[0, 1],
+        )
+        .expect("Should write to Vec");
+
+        assert!(created);
+        assert_eq!(
+            "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d\n\
+             0x343c43A37D37dfF08AE8C4A11544c718AbB4fCF8\n",
+            String::from_utf8(out).expect("Should be UTF-8")
+        );
+
+        let mut out = Vec::new();
+        assert!(!create(&mut out, "0x6ac7ea33", &[0]).expect("Should write to Vec"));
+        assert!(out.is_empty());
+    }
+
+    fn check(command: &Command, format: Format, lines: &[u8]) -> (bool, String) {
+        let mut out = Vec::new();
+        let writer = RecordWriter::new(&mut out, format, command).expect("Should write to Vec");
+        let success = check_lines(
+            lines
+                .split(|byte| *byte == b'\n')
+                .map(|line| Ok(line.to_vec())),
+            writer,
+            &Checksummer::default(),
+            false,
+        )
+        .expect("Should write to Vec");
+
+        (success, String::from_utf8(out).expect("Should be UTF-8"))
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let command = Command::Checksum(Args {
+            addresses: vec![],
+            format: Format::Csv,
+            chain_id: None,
+            normalize_unicode: false,
+        });
+
+        assert_eq!(
+            (
+                false,
+                "line,input,status,output,error,code,message,index,char\n\
+                 1,\"0x,00\",error,,Length,invalid_length,\"invalid address length of 5 bytes, \
+                 expected either 40 or 42 (with prefix)\",,\n"
+                    .to_string()
+            ),
+            check(&command, Format::Csv, b"0x,00")
+        );
+    }
+
+    #[test]
+    fn test_invalid_utf8_lines() {
+        let command = Command::Verify {
+            args: Args {
+                addresses: vec![],
+                format: Format::Ndjson,
+                chain_id: None,
+                normalize_unicode: false,
+            },
+            require_checksum: false,
+        };
+
+        let (success, out) = check(
+            &command,
+            Format::Ndjson,
+            b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\r\n\xff\xfe\n\n  0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\n",
+        );
+        assert!(!success);
+
+        let records = out
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("Should be JSON"))
+            .collect::<Vec<_>>();
+        assert_eq!(3, records.len());
+        assert_eq!(
+            ("valid", 1),
+            (
+                records[0]["status"].as_str().unwrap(),
+                records[0]["line"].as_u64().unwrap()
+            )
+        );
+        assert_eq!("error", records[1]["status"]);
+        assert_eq!("invalid_utf8", records[1]["error"]["code"]);
+        assert_eq!("\u{fffd}\u{fffd}", records[1]["input"]);
+        assert_eq!(
+            ("lowercase", 4),
+            (
+                records[2]["status"].as_str().unwrap(),
+                records[2]["line"].as_u64().unwrap()
+            )
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use super::*;
+use core::{fmt, hash, ops::Deref};
+
+/// A checksummed and prefixed address stored inline, i.e. without allocating.
+///
+/// Dereferences to the `0x` prefixed `str`.
+#[derive(Clone, Copy)]
+pub struct ChecksummedAddress([u8; 42]);
+
+impl ChecksummedAddress {
+    /// The buffer must hold a checksummed and prefixed address
+    pub(crate) fn from_buffer(buffer: [u8; 42]) -> Self {
+        debug_assert!(buffer.starts_with(PREFIX.as_bytes()));
+
+        Self(buffer)
+    }
+
+    pub fn as_str(&self) -> &str {
+        ascii_str(&self.0)
+    }
+
+    /// The checksummed address without the `0x` prefix
+    pub fn hex(&self) -> &str {
+        &self.as_str()[PREFIX.len()..]
+    }
+}
+
+impl Deref for ChecksummedAddress {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ChecksummedAddress {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for ChecksummedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for ChecksummedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for ChecksummedAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for ChecksummedAddress {}
+
+impl hash::Hash for ChecksummedAddress {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+/// Exact (case-sensitive) comparison
+impl PartialEq<str> for ChecksummedAddress {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+/// Exact (case-sensitive) comparison
+impl PartialEq<&str> for ChecksummedAddress {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ChecksummedAddress> for String {
+    fn from(checksummed: ChecksummedAddress) -> Self {
+        checksummed.as_str().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::CHECKSUMMED;
+
+    #[test]
+    fn test_checksummed_address() {
+        let checksummed = Checksum::encode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+            .expect("Should be valid address");
+
+        assert_eq!(checksummed, CHECKSUMMED);
+        assert_eq!(
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            checksummed.hex()
+        );
+        assert_eq!(42, checksummed.len());
+        assert!(checksummed.starts_with(PREFIX));
+
+        let address = Address::parse(&checksummed).expect("Should be valid address");
+        assert_eq!(checksummed, address.checksummed());
+    }
+
+    #[test]
+    fn test_write_checksum() {
+        let address = Address::parse(CHECKSUMMED).expect("Should be valid address");
+
+        let mut fmt_out = String::new();
+        address
+            .write_checksum(&mut fmt_out)
+            .expect("Should write to String");
+
+        assert_eq!(address.checksummed(), fmt_out.as_str());
+        #[cfg(feature = "alloc")]
+        assert_eq!(String::from(address.checksummed()), address.to_string());
+
+        #[cfg(feature = "std")]
+        {
+            let mut io_out = Vec::new();
+            address
+                .write_checksum_io(&mut io_out)
+                .expect("Should write to Vec");
+            assert_eq!(fmt_out.as_bytes(), io_out.as_slice());
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! A `const fn` Keccak-256, used to validate checksums at compile time
+//! and as the portable fallback backend when no other backend is enabled.
+//!
+//! It's a straightforward implementation of the Keccak-f\[1600\] permutation,
+//! slower than `tiny-keccak` or `sha3`.
+
+/// The bytes absorbed per permutation, `1600 - 2 * 256` bits
+pub(crate) const RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001,
+    0x0000_0000_0000_8082,
+    0x8000_0000_0000_808a,
+    0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a,
+    0x0000_0000_0000_0088,
+    0x0000_0000_8000_8009,
+    0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b,
+    0x8000_0000_0000_008b,
+    0x8000_0000_0000_8089,
+    0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002,
+    0x8000_0000_0000_0080,
+    0x0000_0000_0000_800a,
+    0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8080,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8008,
+];
+
+const ROTATIONS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI_LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+const fn keccak_f(mut state: [u64; 25]) -> [u64; 25] {
+    let mut round = 0;
+
+    while round < 24 {
+        // θ
+        let mut columns = [0_u64; 5];
+        let mut x = 0;
+        while x < 5 {
+            columns[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+            x += 1;
+        }
+
+        x = 0;
+        while x < 5 {
+            let d = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+
+            let mut y = 0;
+            while y < 25 {
+                state[y + x] ^= d;
+                y += 5;
+            }
+            x += 1;
+        }
+
+        // ρ and π
+        let mut last = state[1];
+        let mut i = 0;
+        while i < 24 {
+            let lane = PI_LANES[i];
+            let current = state[lane];
+            state[lane] = last.rotate_left(ROTATIONS[i]);
+            last = current;
+            i += 1;
+        }
+
+        // χ
+        let mut y = 0;
+        while y < 25 {
+            let row = [
+                state[y],
+                state[y + 1],
+                state[y + 2],
+                state[y + 3],
+                state[y + 4],
+            ];
+
+            x = 0;
+            while x < 5 {
+                state[y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+                x += 1;
+            }
+            y += 5;
+        }
+
+        // ι
+        state[0] ^= ROUND_CONSTANTS[round];
+        round += 1;
+    }
+
+    state
+}
+
+/// XORs the `RATE` bytes of the block, starting at `offset`, into the state
+pub(crate) const fn absorb(mut state: [u64; 25], block: &[u8], offset: usize) -> [u64; 25] {
+    let mut lane = 0;
+
+    while lane < RATE / 8 {
+        let start = offset + lane * 8;
+        let bytes = [
+            block[start],
+            block[start + 1],
+            block[start + 2],
+            block[start + 3],
+            block[start + 4],
+            block[start + 5],
+            block[start + 6],
+            block[start + 7],
+        ];
+        state[lane] ^= u64::from_le_bytes(bytes);
+        lane += 1;
+    }
+
+    keccak_f(state)
+}
+
+/// Pads the last (incomplete) block of `length` bytes, absorbs it and squeezes the hash
+pub(crate) const fn finalize(
+    state: [u64; 25],
+    mut last_block: [u8; RATE],
+    length: usize,
+) -> [u8; 32] {
+    // the Keccak (not SHA-3) padding
+    last_block[length] ^= 0x01;
+    last_block[RATE - 1] ^= 0x80;
+    let state = absorb(state, &last_block, 0);
+
+    let mut hash = [0_u8; 32];
+    let mut lane = 0;
+    while lane < 4 {
+        let bytes = state[lane].to_le_bytes();
+
+        let mut byte = 0;
+        while byte < 8 {
+            hash[lane * 8 + byte] = bytes[byte];
+            byte += 1;
+        }
+        lane += 1;
+    }
+
+    hash
+}
+
+#[cfg_attr(not(feature = "macros"), allow(dead_code))]
+pub(crate) const fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut state = [0_u64; 25];
+
+    let mut offset = 0;
+    while data.len() - offset >= RATE {
+        state = absorb(state, data, offset);
+        offset += RATE;
+    }
+
+    let mut last_block = [0_u8; RATE];
+    let mut i = 0;
+    while offset + i < data.len() {
+        last_block[i] = data[offset + i];
+        i += 1;
+    }
+
+    finalize(state, last_block, i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_keccak256_empty_input() {
+        let expected = [
+            0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+            0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+            0x5d, 0x85, 0xa4, 0x70,
+        ];
+
+        assert_eq!(expected, keccak256(&[]));
+    }
+
+    #[test]
+    #[cfg(feature = "tiny-keccak")]
+    fn test_const_keccak256_matches_tiny_keccak() {
+        use tiny_keccak::{Hasher, Keccak};
+
+        // around the block boundaries
+        for length in [0, 1, 40, 135, 136, 137, 271, 272, 273, 500].iter() {
+            let data = (0..*length).map(|i| (i * 7 + 3) as u8).collect::<Vec<u8>>();
+
+            let mut expected = [0_u8; 32];
+            let mut hasher = Keccak::v256();
+            hasher.update(&data);
+            hasher.finalize(&mut expected);
+
+            assert_eq!(expected, keccak256(&data), "length {}", length);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Reporting every problem of an address at once, e.g. for form validation.
+use super::*;
+use crate::options::{first_two_chars, UPPERCASE_PREFIX};
+use crate::unicode::{self, Confusable};
+use alloc::vec::Vec;
+
+impl Checksum {
+    /// Reports every problem of a (prefixed or not) address at once, see [`Checksummer::diagnose`].
+    pub fn diagnose(input: &str) -> Vec<Error<'_>> {
+        <Checksummer>::default().diagnose(input)
+    }
+}
+
+impl<H: Keccak256> Checksummer<H> {
+    /// Reports every problem of the input at once, instead of failing on the first one:
+    /// every confusable character, the prefix, the length, every invalid hex character
+    /// and the wrongly cased nibbles, in this order. A valid address has no diagnostics.
+    ///
+    /// The checksum is only verified when the rest of the address is valid,
+    /// and an invalid checksum is always reported, like with [`Checksum::validate`].
+    ///
+    /// ```
+    /// use eth_checksum::{Checksum, Error};
+    ///
+    /// let diagnostics = Checksum::diagnose("0X5aaeb6053f3e94c9b9a09f33669435e7ef1beaZ");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Error::Prefix {
+    ///             expected: "0x",
+    ///             actual: "0X",
+    ///         },
+    ///         Error::Length {
+    ///             expected_either: [40, 42],
+    ///             actual: 41,
+    ///         },
+    ///         Error::HexChar {
+    ///             value: 'Z',
+    ///             index: 40,
+    ///         },
+    ///     ],
+    ///     diagnostics
+    /// );
+    /// ```
+    pub fn diagnose<'a>(&self, input: &'a str) -> Vec<Error<'a>> {
+        let input = self.options.trim(input);
+
+        if self.options.normalize_unicode && !input.is_ascii() {
+            let normalized = unicode::normalize(input);
+
+            return self
+                .diagnose_trimmed(&normalized)
+                .into_iter()
+                .map(|diagnostic| diagnostic.of_normalized(input, &normalized))
+                .collect();
+        }
+
+        self.diagnose_trimmed(input)
+    }
+
+    fn diagnose_trimmed<'a>(&self, input: &'a str) -> Vec<Error<'a>> {
+        let mut diagnostics = unicode::confusables(input)
+            .map(|confusable| Error::Confusable {
+                value: confusable.value,
+                index: confusable.index,
+            })
+            .collect::<Vec<_>>();
+        let confusables = diagnostics.len();
+        let first_two = first_two_chars(input);
+        // already reported, like the invisible characters in the length
+        let is_confusable = |value: char| Confusable::new(value, 0).is_some();
+
+        // a 42 characters input is assumed to be prefixed, even with a wrong prefix
+        let hex = if let Some(hex) = input.strip_prefix(PREFIX) {
+            Some(hex)
+        } else if input.starts_with(UPPERCASE_PREFIX) || input.chars().count() == 42 {
+            let allowed =
+                self.options.allow_uppercase_prefix && input.starts_with(UPPERCASE_PREFIX);
+            if !allowed && !first_two.chars().any(is_confusable) {
+                diagnostics.push(Error::prefix(PREFIX, input));
+            }
+
+            Some(&input[first_two.len()..])
+        } else {
+            None
+        };
+
+        match hex {
+            None if self.options.require_prefix => diagnostics.push(Error::prefix(PREFIX, input)),
+            Some(_) if self.options.forbid_prefix => diagnostics.push(Error::prefix("", input)),
+            _ => {}
+        }
+        let hex = hex.unwrap_or(input);
+
+        let length_diagnostic = hex
+            .chars()
+            .filter(|value| !unicode::is_invisible(*value))
+            .count()
+            != 40;
+        if length_diagnostic {
+            diagnostics.push(Error::Length {
+                expected_either: [40, 42],
+                actual: input.len(),
+            });
+        }
+
+        let hex_char_diagnostics = diagnostics.len();
+        // the chars of the prefix, which may not be ASCII
+        let offset = input[..input.len() - hex.len()].chars().count();
+        diagnostics.extend(
+            hex.chars()
+                .enumerate()
+                .map(|(index, value)| (offset + index, value))
+                .filter(|(_, value)| !value.is_ascii_hexdigit() && !is_confusable(*value))
+                .map(|(index, value)| Error::HexChar { value, index }),
+        );
+
+        if confusables == 0 && !length_diagnostic && diagnostics.len() == hex_char_diagnostics {
+            let (_, verification) =
+                verify_checksum::<H>(hex, self.chain_id).expect("The address is valid hex");
+
+            let checksum_diagnostic = match verification {
+                Verification::Invalid(positions) => Err(Error::Checksum { positions }),
+                verification => self.options.check_checksum(hex, verification),
+            };
+            diagnostics.extend(checksum_diagnostic.err());
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::CHECKSUMMED;
+
+    #[test]
+    fn test_diagnose_valid_addresses() {
+        let valid = [
+            CHECKSUMMED,
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+        ];
+
+        for address in valid.iter() {
+            assert_eq!(Vec::<Error<'_>>::new(), Checksum::diagnose(address));
+        }
+    }
+
+    #[test]
+    fn test_diagnose_reports_every_problem() {
+        // a wrong prefix and two invalid hex characters
+        assert_eq!(
+            vec![
+                Error::Prefix {
+                    expected: PREFIX,
+                    actual: "0y",
+                },
+                Error::HexChar {
+                    value: 'g',
+                    index: 12,
+                },
+                Error::HexChar {
+                    value: '\u{e9}',
+                    index: 41,
+                },
+            ],
+            Checksum::diagnose("0y5aaeb6053fge94c9b9a09f33669435e7ef1beae\u{e9}")
+        );
+
+        // too short and an invalid hex character
+        assert_eq!(
+            vec![
+                Error::Length {
+                    expected_either: [40, 42],
+                    actual: 16,
+                },
+                Error::HexChar {
+                    value: 'q',
+                    index: 5,
+                },
+            ],
+            Checksum::diagnose("0x5aaq6053f3e94c")
+        );
+
+        // `A` at index 2 and `e` at index 39 are wrongly cased
+        match Checksum::diagnose("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").as_slice() {
+            [Error::Checksum { positions }] => {
+                assert_eq!(vec![2, 39], positions.iter().collect::<Vec<_>>())
+            }
+            other => panic!("Expected checksum diagnostic, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_diagnose_with_options() {
+        let lenient = Checksum::with_options(
+            ParseOptions::lenient()
+                .require_prefix(true)
+                .reject_all_lowercase(true),
+        );
+
+        assert_eq!(
+            vec![
+                Error::Prefix {
+                    expected: PREFIX,
+                    actual: "5a",
+                },
+                Error::MissingChecksum,
+            ],
+            lenient.diagnose(" \"5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\" ")
+        );
+        assert!(lenient
+            .diagnose("'0X5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed'\n")
+            .is_empty());
+    }
+}
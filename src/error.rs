@@ -0,0 +1,394 @@
+use super::{options::first_two_chars, unicode::Confusable, Positions};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{fmt, str::Utf8Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error<'a> {
+    /// Invalid length of the address, in bytes
+    Length {
+        expected_either: [usize; 2],
+        actual: usize,
+    },
+    /// Invalid prefix, `actual` holds the first two characters of the input, or those normalized
+    /// to them with [`ParseOptions::normalize_unicode`](crate::ParseOptions::normalize_unicode).
+    ///
+    /// An empty `expected` prefix means that the address must not be prefixed,
+    /// see [`ParseOptions::forbid_prefix`](crate::ParseOptions::forbid_prefix).
+    Prefix {
+        expected: &'static str,
+        actual: &'a str,
+    },
+    Utf8(Utf8Error),
+    /// Invalid Hex character
+    ///
+    /// The `index` is the char index in the (trimmed) input, including the prefix.
+    HexChar {
+        value: char,
+        index: usize,
+    },
+    /// A Unicode look-alike of an ASCII character or an invisible character, see
+    /// [`unicode`](crate::unicode), reported instead of the [`Error::HexChar`] and [`Error::Length`]
+    /// it causes.
+    ///
+    /// The `index` is the char index in the (trimmed) input, including the prefix.
+    Confusable {
+        value: char,
+        index: usize,
+    },
+    /// Mixed-case address with an invalid EIP-55 checksum
+    Checksum {
+        /// The positions of the wrongly cased nibbles
+        positions: Positions,
+    },
+    /// All lowercase or all uppercase address, i.e. without a checksum, rejected by the
+    /// [`ParseOptions`](crate::ParseOptions)
+    MissingChecksum,
+    /// Invalid length of a raw (binary) address
+    ByteLength {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl<'a> Error<'a> {
+    /// An [`Error::Prefix`] holding the first two characters of the input
+    pub(crate) fn prefix(expected: &'static str, input: &'a str) -> Self {
+        Error::Prefix {
+            expected,
+            actual: first_two_chars(input),
+        }
+    }
+
+    /// Moves the index of an [`Error::HexChar`] in the hex address to the input, after its `prefix`
+    pub(crate) fn after_prefix(self, prefix: Option<&str>) -> Self {
+        match self {
+            Error::HexChar { value, index } => Error::HexChar {
+                value,
+                index: index + prefix.map_or(0, str::len),
+            },
+            error => error,
+        }
+    }
+
+    /// A stable, machine-readable code of the error, e.g. for API responses.
+    ///
+    /// The codes will not change between releases, unlike the [`Display`](fmt::Display) messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Length { .. } => "invalid_length",
+            Error::Prefix { .. } => "invalid_prefix",
+            Error::Utf8(_) => "invalid_utf8",
+            Error::HexChar { .. } => "invalid_hex_char",
+            Error::Confusable { .. } => "confusable_char",
+            Error::Checksum { .. } => "invalid_checksum",
+            Error::MissingChecksum => "missing_checksum",
+            Error::ByteLength { .. } => "invalid_byte_length",
+        }
+    }
+
+    /// Converts the error into one that no longer borrows from the input
+    #[cfg(feature = "alloc")]
+    pub fn into_owned(self) -> OwnedError {
+        OwnedError::from(self)
+    }
+
+    /// Maps the part of the input held by an [`Error::Prefix`], the only borrowing variant
+    pub(crate) fn map_prefix<'b>(self, map: impl FnOnce(&'a str) -> &'b str) -> Error<'b> {
+        match self {
+            Error::Length {
+                expected_either,
+                actual,
+            } => Error::Length {
+                expected_either,
+                actual,
+            },
+            Error::Prefix { expected, actual } => Error::Prefix {
+                expected,
+                actual: map(actual),
+            },
+            Error::Utf8(error) => Error::Utf8(error),
+            Error::HexChar { value, index } => Error::HexChar { value, index },
+            Error::Confusable { value, index } => Error::Confusable { value, index },
+            Error::Checksum { positions } => Error::Checksum { positions },
+            Error::MissingChecksum => Error::MissingChecksum,
+            Error::ByteLength { expected, actual } => Error::ByteLength { expected, actual },
+        }
+    }
+}
+
+impl<'a> fmt::Display for Error<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Length {
+                expected_either: [unprefixed, prefixed],
+                actual,
+            } => write!(
+                f,
+                "invalid address length of {} bytes, expected either {} or {} (with prefix)",
+                actual, unprefixed, prefixed
+            ),
+            Error::Prefix {
+                expected: "",
+                actual,
+            } => write!(f, "unexpected address prefix `{}`", actual),
+            Error::Prefix { expected, actual } => write!(
+                f,
+                "invalid address prefix `{}`, expected `{}`",
+                actual, expected
+            ),
+            Error::Utf8(error) => write!(f, "invalid UTF-8 address: {}", error),
+            Error::HexChar { value, index } => {
+                write!(f, "invalid hex character {:?} at index {}", value, index)
+            }
+            Error::Confusable { value, index } => match Confusable::new(*value, *index) {
+                Some(Confusable {
+                    name,
+                    ascii: Some(ascii),
+                    ..
+                }) => write!(
+                    f,
+                    "confusable character {:?} ({}) at index {}, looks like {:?}",
+                    value, name, index, ascii
+                ),
+                Some(Confusable { name, .. }) => write!(
+                    f,
+                    "invisible character {:?} ({}) at index {}",
+                    value, name, index
+                ),
+                None => write!(f, "confusable character {:?} at index {}", value, index),
+            },
+            Error::Checksum { positions } => write!(
+                f,
+                "invalid checksum, wrongly cased characters at {:?}",
+                positions
+            ),
+            Error::MissingChecksum => {
+                f.write_str("address without a checksum, all letters are of the same case")
+            }
+            Error::ByteLength { expected, actual } => write!(
+                f,
+                "invalid address length of {} bytes, expected {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::error::Error for Error<'a> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Utf8(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> From<Utf8Error> for Error<'a> {
+    fn from(e: Utf8Error) -> Self {
+        Self::Utf8(e)
+    }
+}
+
+/// An [`Error`] that doesn't borrow from the input, see [`Error::into_owned`] and
+/// [`InputError::into_owned`](crate::InputError::into_owned)
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct OwnedError {
+    /// The error, with the prefix of an [`Error::Prefix`] moved to `prefix`
+    error: Error<'static>,
+    prefix: String,
+    pub(crate) input: Option<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedError {
+    pub(crate) fn with_input(error: Error<'_>, input: &str) -> Self {
+        Self {
+            input: Some(String::from(input)),
+            ..Self::from(error)
+        }
+    }
+
+    pub fn error(&self) -> Error<'_> {
+        self.error.clone().map_prefix(|_| &self.prefix)
+    }
+
+    /// The input of the error, if it was attached with [`Error::with_input`]
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    /// The code of the error, see [`Error::code`]
+    pub fn code(&self) -> &'static str {
+        self.error.code()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Error<'_>> for OwnedError {
+    fn from(error: Error<'_>) -> Self {
+        let mut prefix = String::new();
+        let error = error.map_prefix(|actual| {
+            prefix.push_str(actual);
+            ""
+        });
+
+        Self {
+            error,
+            prefix,
+            input: None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for OwnedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedError")
+            .field("error", &self.error())
+            .field("input", &self.input)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for OwnedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OwnedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+/// An invalid secp256k1 key, see [`Address::from_public_key`](crate::Address::from_public_key)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// Invalid length of an uncompressed public key, in bytes
+    PublicKeyLength { actual: usize },
+    /// Invalid prefix of a 65 bytes public key, which must be the `0x04` of an uncompressed key
+    PublicKeyPrefix { actual: u8 },
+    /// A compressed public key which isn't a point of the secp256k1 curve
+    PublicKeyPoint,
+    /// A private key of zero or not below the secp256k1 curve order
+    PrivateKey,
+}
+
+impl KeyError {
+    /// A stable, machine-readable code of the error, see [`Error::code`]
+    pub fn code(&self) -> &'static str {
+        match self {
+            KeyError::PublicKeyLength { .. } => "invalid_public_key_length",
+            KeyError::PublicKeyPrefix { .. } => "invalid_public_key_prefix",
+            KeyError::PublicKeyPoint => "invalid_public_key_point",
+            KeyError::PrivateKey => "invalid_private_key",
+        }
+    }
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::PublicKeyLength { actual } => write!(
+                f,
+                "invalid public key length of {} bytes, expected {}64 or 65 (with prefix)",
+                actual,
+                if cfg!(feature = "k256") {
+                    "33 (compressed), "
+                } else {
+                    "either "
+                }
+            ),
+            KeyError::PublicKeyPrefix { actual } => write!(
+                f,
+                "invalid public key prefix `{:#04x}`, expected `0x04` (uncompressed)",
+                actual
+            ),
+            KeyError::PublicKeyPoint => f.write_str("invalid public key, not a point of secp256k1"),
+            KeyError::PrivateKey => f.write_str("invalid private key, zero or not below the order"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyError {}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use super::*;
+    use crate::Checksum;
+    #[cfg(feature = "std")]
+    use crate::TryChecksum;
+    #[cfg(feature = "std")]
+    use std::error::Error as _;
+
+    #[test]
+    fn test_error_display_and_code() {
+        let length = Checksum::encode("0x1234").unwrap_err();
+        assert_eq!("invalid_length", length.code());
+        assert_eq!(
+            "invalid address length of 6 bytes, expected either 40 or 42 (with prefix)",
+            length.to_string()
+        );
+
+        let prefix = Checksum::encode("0Xe0fc04fa2d34a66b779fd5cee748268032a146c0").unwrap_err();
+        assert_eq!("invalid_prefix", prefix.code());
+        assert_eq!(
+            "invalid address prefix `0X`, expected `0x`",
+            prefix.to_string()
+        );
+
+        let hex_char = Checksum::encode("e0fc04fa2d34a66b779fd5cee748268032a146cg").unwrap_err();
+        assert_eq!("invalid_hex_char", hex_char.code());
+        assert_eq!(
+            "invalid hex character 'g' at index 39",
+            hex_char.to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_owned_error_and_source() {
+        fn boxed(input: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(Checksum::from_str(input).map_err(Error::into_owned)?)
+        }
+
+        let error = boxed(&String::from("0y5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"))
+            .expect_err("Should be an invalid prefix");
+        assert_eq!(
+            "invalid address prefix `0y`, expected `0x`",
+            error.to_string()
+        );
+
+        let owned = Checksum::encode("0y5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+            .unwrap_err()
+            .into_owned();
+        assert_eq!(
+            Error::Prefix {
+                expected: "0x",
+                actual: "0y",
+            },
+            owned.error()
+        );
+        assert_eq!("invalid_prefix", owned.code());
+        assert_eq!(None, owned.input());
+
+        let mut invalid_utf8 = [b'0'; 40];
+        invalid_utf8[0] = 0xff;
+        let utf8 = invalid_utf8.try_checksum().unwrap_err();
+        assert_eq!("invalid_utf8", utf8.code());
+        assert!(utf8.source().is_some());
+        assert!(Error::Checksum {
+            positions: Positions::default()
+        }
+        .source()
+        .is_none());
+    }
+}
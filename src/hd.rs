@@ -0,0 +1,760 @@
+//! BIP-32 hierarchical deterministic keys, to derive the addresses of a wallet from its seed
+//! or, watch-only, from an extended public key (`xpub`), enabled with the `k256` feature.
+//!
+//! The Ethereum accounts follow BIP-44, i.e. the receive addresses are at
+//! `m/44'/60'/0'/0/i`, and the `xpub` shared with a watch-only service is the one of the
+//! account, [`ETHEREUM_ACCOUNT`].
+//!
+//! ```
+//! use eth_checksum::hd::{ExtendedPrivateKey, ExtendedPublicKey, ETHEREUM_ACCOUNT};
+//!
+//! // the seed of the `abandon abandon ... about` BIP-39 mnemonic, without a passphrase
+//! let seed = [
+//!     0x5e, 0xb0, 0x0b, 0xbd, 0xdc, 0xf0, 0x69, 0x08, 0x48, 0x89, 0xa8, 0xab, 0x91, 0x55, 0x56,
+//!     0x81, 0x65, 0xf5, 0xc4, 0x53, 0xcc, 0xb8, 0x5e, 0x70, 0x81, 0x1a, 0xae, 0xd6, 0xf6, 0xda,
+//!     0x5f, 0xc1, 0x9a, 0x5a, 0xc4, 0x0b, 0x38, 0x9c, 0xd3, 0x70, 0xd0, 0x86, 0x20, 0x6d, 0xec,
+//!     0x8a, 0xa6, 0xc4, 0x3d, 0xae, 0xa6, 0x69, 0x0f, 0x20, 0xad, 0x3d, 0x8d, 0x48, 0xb2, 0xd2,
+//!     0xce, 0x9e, 0x38, 0xe4,
+//! ];
+//! let account = ExtendedPrivateKey::from_seed(&seed)
+//!     .and_then(|master| master.derive_path(ETHEREUM_ACCOUNT))
+//!     .unwrap();
+//!
+//! // the watch-only side only knows the `xpub` of the account
+//! let xpub: ExtendedPublicKey = account.public_key().to_string().parse().unwrap();
+//! let (index, address) = xpub.receive_addresses(0..20).unwrap().next().unwrap();
+//!
+//! assert_eq!(0, index);
+//! assert_eq!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", address.to_string());
+//! ```
+use crate::Address;
+use core::{fmt, ops::Range, str::FromStr};
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::{sec1::ToEncodedPoint, PrimeField};
+use k256::{NonZeroScalar, ProjectivePoint, PublicKey, Scalar, SecretKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+
+/// The offset of the hardened child indexes, written `i'` or `iH` in a path
+pub const HARDENED: u32 = 1 << 31;
+
+/// The BIP-44 path of the first Ethereum account, whose external chain `/0` holds
+/// the receive addresses
+pub const ETHEREUM_ACCOUNT: &str = "m/44'/60'/0'";
+
+/// The version bytes of the mainnet extended private keys, `xprv`
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+/// The version bytes of the mainnet extended public keys, `xpub`
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+/// The length of a serialized extended key, with its checksum
+const SERIALIZED_LEN: usize = 82;
+/// The maximum number of base58 digits of a serialized extended key
+const BASE58_LEN: usize = 112;
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerivationError {
+    /// Invalid length of the seed, in bytes, which must be between 16 and 64
+    SeedLength { actual: usize },
+    /// Invalid derivation path
+    Path,
+    /// Hardened child of an extended public key
+    Hardened { index: u32 },
+    /// A child key that is invalid, with a probability lower than 2^-127, the next index
+    /// should be used instead
+    InvalidChild { index: u32 },
+    /// A child deeper than the 255 levels of an extended key
+    Depth,
+    /// Invalid base58 character or checksum of an extended key
+    Base58,
+    /// Invalid length of a serialized extended key
+    Length,
+    /// Unknown version bytes of a serialized extended key, e.g. of a private key or a testnet one
+    Version { actual: [u8; 4] },
+    /// Invalid key of a serialized extended key
+    Key,
+    /// A serialized master key, i.e. of depth 0, with a parent fingerprint or a child number
+    Master,
+}
+
+impl fmt::Display for DerivationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivationError::SeedLength { actual } => write!(
+                f,
+                "invalid seed length of {} bytes, expected between 16 and 64",
+                actual
+            ),
+            DerivationError::Path => f.write_str("invalid derivation path"),
+            DerivationError::Hardened { index } => write!(
+                f,
+                "hardened child {}' can't be derived from a public key",
+                index & !HARDENED
+            ),
+            DerivationError::InvalidChild { index } => {
+                write!(f, "invalid child key at index {}", index)
+            }
+            DerivationError::Depth => f.write_str("extended key deeper than 255 levels"),
+            DerivationError::Base58 => f.write_str("invalid base58 extended key"),
+            DerivationError::Length => f.write_str("invalid extended key length"),
+            DerivationError::Version { actual } => write!(
+                f,
+                "invalid extended key version `{:02x}{:02x}{:02x}{:02x}`, expected an `xpub`",
+                actual[0], actual[1], actual[2], actual[3]
+            ),
+            DerivationError::Key => f.write_str("invalid key of the extended key"),
+            DerivationError::Master => f.write_str(
+                "invalid master extended key, with a parent fingerprint or a child number",
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DerivationError {}
+
+/// The position of an extended key in its tree, shared by the private and the public keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Node {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+}
+
+impl Node {
+    /// The node of the child, from the right half of its HMAC-SHA512
+    fn child(
+        &self,
+        parent_key: &PublicKey,
+        index: u32,
+        chain_code: &[u8],
+    ) -> Result<Self, DerivationError> {
+        let mut child = Node {
+            depth: self.depth.checked_add(1).ok_or(DerivationError::Depth)?,
+            parent_fingerprint: [0; 4],
+            child_number: index,
+            chain_code: [0; 32],
+        };
+        child
+            .parent_fingerprint
+            .copy_from_slice(&key_id(parent_key)[..4]);
+        child.chain_code.copy_from_slice(chain_code);
+
+        Ok(child)
+    }
+
+    /// The 78 bytes serialization of the key, followed by its checksum
+    fn serialize(&self, version: [u8; 4], key: &[u8; 33]) -> [u8; SERIALIZED_LEN] {
+        let mut bytes = [0_u8; SERIALIZED_LEN];
+        bytes[..4].copy_from_slice(&version);
+        bytes[4] = self.depth;
+        bytes[5..9].copy_from_slice(&self.parent_fingerprint);
+        bytes[9..13].copy_from_slice(&self.child_number.to_be_bytes());
+        bytes[13..45].copy_from_slice(&self.chain_code);
+        bytes[45..78].copy_from_slice(key);
+
+        let checksum = checksum(&bytes[..78]);
+        bytes[78..].copy_from_slice(&checksum);
+
+        bytes
+    }
+}
+
+/// An extended private key, from which both the private and the public children can be derived
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedPrivateKey {
+    private_key: SecretKey,
+    node: Node,
+}
+
+impl ExtendedPrivateKey {
+    /// The master key of a (BIP-39) seed of 16 to 64 bytes
+    pub fn from_seed(seed: &[u8]) -> Result<Self, DerivationError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(DerivationError::SeedLength { actual: seed.len() });
+        }
+
+        let hmac = hmac_sha512(b"Bitcoin seed", &[seed]);
+        let private_key = SecretKey::from_slice(&hmac[..32]).map_err(|_| DerivationError::Key)?;
+        let mut node = Node {
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            chain_code: [0; 32],
+        };
+        node.chain_code.copy_from_slice(&hmac[32..]);
+
+        Ok(Self { private_key, node })
+    }
+
+    /// Derives the child at the `index`, hardened from [`HARDENED`] on
+    pub fn derive_child(&self, index: u32) -> Result<Self, DerivationError> {
+        let public_key = self.private_key.public_key();
+        let hmac = if index >= HARDENED {
+            let private_key = self.private_key.to_bytes();
+            hmac_sha512(
+                &self.node.chain_code,
+                &[&[0], &private_key, &index.to_be_bytes()],
+            )
+        } else {
+            hmac_sha512(
+                &self.node.chain_code,
+                &[&compressed(&public_key), &index.to_be_bytes()],
+            )
+        };
+
+        let private_key = tweak(&hmac[..32])
+            .and_then(|tweak| {
+                Option::from(NonZeroScalar::new(
+                    tweak + *self.private_key.to_nonzero_scalar(),
+                ))
+            })
+            .map(|scalar: NonZeroScalar| SecretKey::from(scalar))
+            .ok_or(DerivationError::InvalidChild { index })?;
+
+        Ok(Self {
+            private_key,
+            node: self.node.child(&public_key, index, &hmac[32..])?,
+        })
+    }
+
+    /// Derives the descendant at the `path`, e.g. `m/44'/60'/0'/0/0`, relative to this key
+    pub fn derive_path(&self, path: &str) -> Result<Self, DerivationError> {
+        let mut key = self.clone();
+        for index in parse_path(path) {
+            key = key.derive_child(index?)?;
+        }
+
+        Ok(key)
+    }
+
+    /// The extended public key, from which the non-hardened public children can be derived
+    pub fn public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            public_key: self.private_key.public_key(),
+            node: self.node,
+        }
+    }
+
+    /// The address of the key
+    pub fn address(&self) -> Address {
+        Address::from(self.private_key.public_key())
+    }
+
+    /// The 32 bytes of the private key
+    pub fn private_key(&self) -> [u8; 32] {
+        self.private_key.to_bytes().into()
+    }
+
+    /// Writes the `xprv` serialization of the key.
+    ///
+    /// It isn't [`Display`](fmt::Display), so that the private key is only ever written on purpose.
+    pub fn write_xprv<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let mut key = [0_u8; 33];
+        key[1..].copy_from_slice(&self.private_key.to_bytes());
+
+        write_base58(writer, &self.node.serialize(XPRV_VERSION, &key))
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("depth", &self.node.depth)
+            .field("child_number", &self.node.child_number)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An extended public key, from which the non-hardened children can be derived,
+/// e.g. by a watch-only service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    public_key: PublicKey,
+    node: Node,
+}
+
+impl ExtendedPublicKey {
+    /// Parses an `xpub`
+    pub fn parse(input: &str) -> Result<Self, DerivationError> {
+        let bytes = read_base58(input)?;
+        if checksum(&bytes[..78]) != bytes[78..] {
+            return Err(DerivationError::Base58);
+        }
+
+        let mut version = [0_u8; 4];
+        version.copy_from_slice(&bytes[..4]);
+        if version != XPUB_VERSION {
+            return Err(DerivationError::Version { actual: version });
+        }
+
+        let mut node = Node {
+            depth: bytes[4],
+            parent_fingerprint: [0; 4],
+            child_number: u32::from_be_bytes([bytes[9], bytes[10], bytes[11], bytes[12]]),
+            chain_code: [0; 32],
+        };
+        node.parent_fingerprint.copy_from_slice(&bytes[5..9]);
+        node.chain_code.copy_from_slice(&bytes[13..45]);
+        if node.depth == 0 && (node.parent_fingerprint != [0; 4] || node.child_number != 0) {
+            return Err(DerivationError::Master);
+        }
+
+        let public_key = match bytes[45] {
+            0x02 | 0x03 => PublicKey::from_sec1_bytes(&bytes[45..78]).ok(),
+            _ => None,
+        };
+
+        Ok(Self {
+            public_key: public_key.ok_or(DerivationError::Key)?,
+            node,
+        })
+    }
+
+    /// Derives the non-hardened child at the `index`
+    pub fn derive_child(&self, index: u32) -> Result<Self, DerivationError> {
+        if index >= HARDENED {
+            return Err(DerivationError::Hardened { index });
+        }
+
+        let hmac = hmac_sha512(
+            &self.node.chain_code,
+            &[&compressed(&self.public_key), &index.to_be_bytes()],
+        );
+        let public_key = tweak(&hmac[..32])
+            .and_then(|tweak| {
+                let point = ProjectivePoint::GENERATOR * tweak + self.public_key.to_projective();
+                PublicKey::from_affine(point.to_affine()).ok()
+            })
+            .ok_or(DerivationError::InvalidChild { index })?;
+
+        Ok(Self {
+            public_key,
+            node: self.node.child(&self.public_key, index, &hmac[32..])?,
+        })
+    }
+
+    /// Derives the descendant at the `path`, e.g. `0/0` or `m/0/0`, relative to this key
+    pub fn derive_path(&self, path: &str) -> Result<Self, DerivationError> {
+        let mut key = *self;
+        for index in parse_path(path) {
+            key = key.derive_child(index?)?;
+        }
+
+        Ok(key)
+    }
+
+    /// The address of the key
+    pub fn address(&self) -> Address {
+        Address::from(self.public_key)
+    }
+
+    /// The receive addresses of an account key, i.e. of its external chain `/0`,
+    /// along with their index.
+    ///
+    /// The indexes of the (improbable) invalid children are skipped, as BIP-32 requires,
+    /// while the other errors are of the whole range, e.g. a [`DerivationError::Hardened`]
+    /// for a range reaching the hardened indexes.
+    pub fn receive_addresses(
+        &self,
+        indexes: Range<u32>,
+    ) -> Result<impl Iterator<Item = (u32, Address)>, DerivationError> {
+        let external = self.derive_child(0)?;
+        if !indexes.is_empty() {
+            if indexes.end > HARDENED {
+                return Err(DerivationError::Hardened {
+                    index: indexes.start.max(HARDENED),
+                });
+            }
+            if external.node.depth == u8::MAX {
+                return Err(DerivationError::Depth);
+            }
+        }
+
+        Ok(indexes.filter_map(move |index| {
+            external
+                .derive_child(index)
+                .ok()
+                .map(|child| (index, child.address()))
+        }))
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_base58(
+            f,
+            &self
+                .node
+                .serialize(XPUB_VERSION, &compressed(&self.public_key)),
+        )
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = DerivationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+/// The child indexes of a path, with an optional `m/` prefix and `'`, `h` or `H`
+/// marking the hardened indexes
+fn parse_path(path: &str) -> impl Iterator<Item = Result<u32, DerivationError>> + '_ {
+    let indexes = match path {
+        "m" | "M" | "" => None,
+        _ => Some(
+            path.strip_prefix("m/")
+                .or_else(|| path.strip_prefix("M/"))
+                .unwrap_or(path),
+        ),
+    };
+
+    indexes
+        .into_iter()
+        .flat_map(|indexes| indexes.split('/'))
+        .map(|index| {
+            let (digits, hardened) = match index.strip_suffix(&['\'', 'h', 'H'][..]) {
+                Some(digits) => (digits, HARDENED),
+                None => (index, 0),
+            };
+            if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(DerivationError::Path);
+            }
+
+            match digits.parse::<u32>() {
+                Ok(index) if index < HARDENED => Ok(index + hardened),
+                _ => Err(DerivationError::Path),
+            }
+        })
+}
+
+/// The left half of a child's HMAC-SHA512 as a scalar, `None` if not below the curve order
+fn tweak(bytes: &[u8]) -> Option<Scalar> {
+    let mut repr = k256::FieldBytes::default();
+    repr.copy_from_slice(bytes);
+
+    Option::from(Scalar::from_repr(repr))
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    for data in data {
+        mac.update(data);
+    }
+
+    mac.finalize().into_bytes().into()
+}
+
+fn compressed(public_key: &PublicKey) -> [u8; 33] {
+    let mut bytes = [0_u8; 33];
+    bytes.copy_from_slice(public_key.to_encoded_point(true).as_bytes());
+
+    bytes
+}
+
+/// The RIPEMD-160 of the SHA-256 of the compressed key, whose first 4 bytes are its fingerprint
+fn key_id(public_key: &PublicKey) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(compressed(public_key))).into()
+}
+
+/// The first 4 bytes of the double SHA-256, of the base58check encoding
+fn checksum(bytes: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(bytes));
+
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn write_base58<W: fmt::Write>(writer: &mut W, bytes: &[u8; SERIALIZED_LEN]) -> fmt::Result {
+    // the little-endian base58 digits
+    let mut digits = [0_u8; BASE58_LEN];
+    let mut len = 0;
+    for &byte in bytes.iter() {
+        let mut carry = u32::from(byte);
+        for digit in digits[..len].iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    for _ in 0..zeros {
+        writer.write_char('1')?;
+    }
+    digits[..len]
+        .iter()
+        .rev()
+        .try_for_each(|&digit| writer.write_char(char::from(BASE58_ALPHABET[usize::from(digit)])))
+}
+
+fn read_base58(input: &str) -> Result<[u8; SERIALIZED_LEN], DerivationError> {
+    if input.len() > BASE58_LEN {
+        return Err(DerivationError::Length);
+    }
+
+    // the big-endian bytes
+    let mut bytes = [0_u8; SERIALIZED_LEN];
+    for byte in input.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&digit| digit == byte)
+            .ok_or(DerivationError::Base58)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        if carry > 0 {
+            return Err(DerivationError::Length);
+        }
+    }
+
+    // the leading `1`s are the leading zeros
+    let ones = input.bytes().take_while(|&byte| byte == b'1').count();
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    if ones + SERIALIZED_LEN - zeros != SERIALIZED_LEN {
+        return Err(DerivationError::Length);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::hex_bytes;
+
+    fn xprv(key: &ExtendedPrivateKey) -> String {
+        let mut xprv = String::new();
+        key.write_xprv(&mut xprv).unwrap();
+
+        xprv
+    }
+
+    #[test]
+    fn test_bip32_vector_1() {
+        let master = ExtendedPrivateKey::from_seed(&hex_bytes("000102030405060708090a0b0c0d0e0f"))
+            .expect("Should be a seed");
+
+        let vectors = [
+            (
+                "m",
+                "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ),
+            (
+                "m/0H",
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            ),
+            (
+                "m/0H/1",
+                "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            ),
+            (
+                "m/0H/1/2H",
+                "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            ),
+            (
+                "m/0H/1/2H/2",
+                "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+                "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+            ),
+            (
+                "m/0H/1/2H/2/1000000000",
+                "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+                "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            ),
+        ];
+
+        for (path, xpub, xprv_) in vectors.iter() {
+            let key = master.derive_path(path).expect("Should be a path");
+            assert_eq!(*xpub, key.public_key().to_string(), "{}", path);
+            assert_eq!(*xprv_, xprv(&key), "{}", path);
+            assert_eq!(Ok(key.public_key()), xpub.parse(), "{}", path);
+        }
+
+        // the public derivation of the non-hardened children
+        let parsed = ExtendedPublicKey::parse(vectors[1].1).unwrap();
+        assert_eq!(vectors[2].1, parsed.derive_child(1).unwrap().to_string());
+        let parsed = ExtendedPublicKey::parse(vectors[3].1).unwrap();
+        assert_eq!(
+            vectors[5].1,
+            parsed.derive_path("2/1000000000").unwrap().to_string()
+        );
+        assert_eq!(
+            parsed.derive_child(2).unwrap().address(),
+            master.derive_path("m/0'/1/2'/2").unwrap().address()
+        );
+    }
+
+    #[test]
+    fn test_bip32_vector_2() {
+        let seed = hex_bytes(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        );
+        let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+
+        assert_eq!(
+            "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+            master.public_key().to_string()
+        );
+        assert_eq!(
+            "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+            master.public_key().derive_child(0).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_bip32_vector_5() {
+        let invalid = [
+            // pubkey version / prvkey mismatch
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+                DerivationError::Key,
+            ),
+            // invalid pubkey prefix 04
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+                DerivationError::Key,
+            ),
+            // invalid pubkey prefix 01
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+                DerivationError::Key,
+            ),
+            // zero depth with non-zero parent fingerprint
+            (
+                "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+                DerivationError::Master,
+            ),
+            // zero depth with non-zero index
+            (
+                "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+                DerivationError::Master,
+            ),
+            // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY",
+                DerivationError::Key,
+            ),
+        ];
+        for (xpub, error) in invalid.iter() {
+            assert_eq!(Err(*error), xpub.parse::<ExtendedPublicKey>(), "{}", xpub);
+        }
+    }
+
+    #[test]
+    fn test_ethereum_receive_addresses() {
+        let seed = hex_bytes(
+            "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4",
+        );
+        let account = ExtendedPrivateKey::from_seed(&seed)
+            .and_then(|master| master.derive_path(ETHEREUM_ACCOUNT))
+            .unwrap();
+
+        let addresses = account
+            .public_key()
+            .receive_addresses(0..3)
+            .unwrap()
+            .map(|(_, address)| address.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+                "0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0",
+                "0xb6716976A3ebe8D39aCEB04372f22Ff8e6802D7A",
+            ],
+            addresses
+        );
+
+        let first = account.derive_path("0/0").unwrap();
+        assert_eq!(addresses[0], first.address().to_string());
+        assert_eq!(
+            Ok(first.address()),
+            Address::from_private_key(&first.private_key())
+        );
+    }
+
+    #[test]
+    fn test_derivation_errors() {
+        assert_eq!(
+            Err(DerivationError::SeedLength { actual: 15 }),
+            ExtendedPrivateKey::from_seed(&[0; 15])
+        );
+
+        let master = ExtendedPrivateKey::from_seed(&[0; 16]).unwrap();
+        for path in ["m/", "m/0/", "m/a", "m/0''", "m/-1", "m/2147483648", "x/0"].iter() {
+            assert_eq!(
+                Err(DerivationError::Path),
+                master.derive_path(path),
+                "{}",
+                path
+            );
+        }
+        assert_eq!(Ok(master.clone()), master.derive_path("m"));
+
+        assert_eq!(
+            Err(DerivationError::Hardened { index: HARDENED }),
+            master.public_key().derive_path("0'")
+        );
+        assert_eq!(
+            "hardened child 0' can't be derived from a public key",
+            DerivationError::Hardened { index: HARDENED }.to_string()
+        );
+        // built by hand, below the hardened indexes
+        assert_eq!(
+            "hardened child 5' can't be derived from a public key",
+            DerivationError::Hardened { index: 5 }.to_string()
+        );
+        assert_eq!(
+            Some(DerivationError::Hardened { index: HARDENED }),
+            master
+                .public_key()
+                .receive_addresses(HARDENED - 2..HARDENED + 2)
+                .err()
+        );
+        assert_eq!(
+            2,
+            master
+                .public_key()
+                .receive_addresses(HARDENED - 2..HARDENED)
+                .unwrap()
+                .count()
+        );
+
+        let xpub = master.public_key().to_string();
+        let mut mistyped = xpub.clone();
+        mistyped.replace_range(20..21, if &xpub[20..21] == "a" { "b" } else { "a" });
+        assert_eq!(
+            Err(DerivationError::Base58),
+            mistyped.parse::<ExtendedPublicKey>()
+        );
+        assert_eq!(
+            Err(DerivationError::Base58),
+            xpub.replace(&xpub[20..21], "0")
+                .parse::<ExtendedPublicKey>()
+        );
+        assert_eq!(
+            Err(DerivationError::Length),
+            xpub[..100].parse::<ExtendedPublicKey>()
+        );
+        assert_eq!(
+            Err(DerivationError::Version {
+                actual: XPRV_VERSION
+            }),
+            xprv(&master).parse::<ExtendedPublicKey>()
+        );
+    }
+}
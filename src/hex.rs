@@ -0,0 +1,553 @@
+//! Byte oriented validation and casing of the 40 hex characters of an address.
+//!
+//! The scalar path uses a 256-entry lookup table. The `simd` feature adds the SSE2 and AVX2
+//! paths on `x86` and `x86_64`, and the NEON path on `aarch64`. Their support is detected at
+//! runtime with the `std` feature, and at compile time (i.e. with `-C target-feature`) without it.
+//! Every other target, or CPU, keeps using the lookup table.
+
+/// Lowercases the hex address, or returns the index of the first non hex byte
+pub(crate) fn to_lowercase(address: &[u8; 40], lowercase: &mut [u8; 40]) -> Result<(), usize> {
+    #[cfg(feature = "simd")]
+    {
+        if simd::to_lowercase(address, lowercase) {
+            return Ok(());
+        }
+    }
+
+    // also the slow path of the SIMD ones, to find the first invalid byte
+    scalar::to_lowercase(address, lowercase)
+}
+
+/// Uppercases the letters of the lowercase hex address at which the hash nibble is `>= 8`
+pub(crate) fn apply_hash(lowercase: &mut [u8; 40], hash: &[u8; 32]) {
+    #[cfg(feature = "simd")]
+    {
+        if simd::apply_hash(lowercase, hash) {
+            return;
+        }
+    }
+
+    scalar::apply_hash(lowercase, hash);
+}
+
+/// The digit commonly confused with the character, e.g. by OCR or when copying by hand
+pub(crate) fn confusable_digit(char: char) -> Option<u8> {
+    match char {
+        'o' | 'O' => Some(b'0'),
+        'l' | 'I' => Some(b'1'),
+        'S' => Some(b'5'),
+        _ => None,
+    }
+}
+
+mod scalar {
+    /// The lowercase hex character of every byte, `0` for the non hex ones
+    static LOWERCASE_HEX: [u8; 256] = lowercase_hex_table();
+
+    const fn lowercase_hex_table() -> [u8; 256] {
+        let mut table = [0_u8; 256];
+
+        let mut byte = 0;
+        while byte < 256 {
+            table[byte] = match byte as u8 {
+                b'0'..=b'9' | b'a'..=b'f' => byte as u8,
+                b'A'..=b'F' => (byte as u8).to_ascii_lowercase(),
+                _ => 0,
+            };
+            byte += 1;
+        }
+
+        table
+    }
+
+    pub(super) fn to_lowercase(address: &[u8; 40], lowercase: &mut [u8; 40]) -> Result<(), usize> {
+        for (i, (byte, lowercase)) in address.iter().zip(lowercase.iter_mut()).enumerate() {
+            match LOWERCASE_HEX[*byte as usize] {
+                // fail on the first invalid byte
+                0 => return Err(i),
+                hex => *lowercase = hex,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn apply_hash(lowercase: &mut [u8; 40], hash: &[u8; 32]) {
+        for (i, byte) in lowercase.iter_mut().enumerate() {
+            if byte.is_ascii_lowercase() && should_be_uppercased(hash, i) {
+                byte.make_ascii_uppercase();
+            }
+        }
+    }
+
+    fn should_be_uppercased(hash: &[u8; 32], i: usize) -> bool {
+        let half_byte_at: u8 = if i & 1 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0x0f
+        };
+
+        half_byte_at >= 8
+    }
+}
+
+/// The dispatch to the SIMD path supported by the CPU, both functions return `false` without one
+#[cfg(feature = "simd")]
+mod simd {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    use super::{avx2, sse2};
+
+    /// Lowercases the hex address, returns `false` on any non hex byte
+    pub(super) fn to_lowercase(address: &[u8; 40], lowercase: &mut [u8; 40]) -> bool {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            // SAFETY: the target feature is supported
+            if avx2::is_supported() {
+                return unsafe { avx2::to_lowercase(address, lowercase) };
+            }
+            if sse2::is_supported() {
+                return unsafe { sse2::to_lowercase(address, lowercase) };
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            // SAFETY: the target feature is supported
+            if super::neon::is_supported() {
+                return unsafe { super::neon::to_lowercase(address, lowercase) };
+            }
+        }
+
+        let _ = (address, lowercase);
+        false
+    }
+
+    /// Applies the hash, returns `false` when no SIMD path is supported
+    pub(super) fn apply_hash(lowercase: &mut [u8; 40], hash: &[u8; 32]) -> bool {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            // SAFETY: the target feature is supported
+            if avx2::is_supported() {
+                unsafe { avx2::apply_hash(lowercase, hash) };
+                return true;
+            }
+            if sse2::is_supported() {
+                unsafe { sse2::apply_hash(lowercase, hash) };
+                return true;
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            // SAFETY: the target feature is supported
+            if super::neon::is_supported() {
+                unsafe { super::neon::apply_hash(lowercase, hash) };
+                return true;
+            }
+        }
+
+        let _ = (lowercase, hash);
+        false
+    }
+}
+
+/// Whether the `x86` or `x86_64` target feature is supported, see the module documentation
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! is_x86_feature_supported {
+    ($feature:tt) => {{
+        #[cfg(feature = "std")]
+        {
+            std::is_x86_feature_detected!($feature)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            cfg!(target_feature = $feature)
+        }
+    }};
+}
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+mod sse2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    /// The offsets of the 16-byte chunks, the last one overlaps the second to cover all 40 bytes.
+    /// Both operations are idempotent, so the overlapping bytes can be processed twice.
+    const OFFSETS: [usize; 3] = [0, 16, 24];
+
+    /// SSE2 is part of the `x86_64` baseline, but not of the `x86` one
+    pub(super) fn is_supported() -> bool {
+        is_x86_feature_supported!("sse2")
+    }
+
+    /// Lowercases the hex address, returns `false` on any non hex byte
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn to_lowercase(address: &[u8; 40], lowercase: &mut [u8; 40]) -> bool {
+        let mut valid = true;
+
+        for &offset in OFFSETS.iter() {
+            // SAFETY: the chunk is within the 40 bytes of both arrays
+            unsafe {
+                let chunk = _mm_loadu_si128(address.as_ptr().add(offset) as *const __m128i);
+                // lowercases the letters and keeps the digits, which already have the bit set
+                let lower = _mm_or_si128(chunk, _mm_set1_epi8(0x20));
+                let hex = _mm_or_si128(in_range(chunk, b'0', b'9'), in_range(lower, b'a', b'f'));
+
+                valid &= _mm_movemask_epi8(hex) == 0xffff;
+                _mm_storeu_si128(lowercase.as_mut_ptr().add(offset) as *mut __m128i, lower);
+            }
+        }
+
+        valid
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn apply_hash(lowercase: &mut [u8; 40], hash: &[u8; 32]) {
+        for &offset in OFFSETS.iter() {
+            // SAFETY: the chunk is within the 40 bytes of the address
+            // and the 8 hash bytes of the chunk are within the 32 bytes of the hash
+            unsafe {
+                let hash_bytes = _mm_loadl_epi64(hash.as_ptr().add(offset / 2) as *const __m128i);
+                // every hash byte twice, i.e. once per nibble
+                let nibbles = _mm_unpacklo_epi8(hash_bytes, hash_bytes);
+                // the high bit of the high nibble for the even and of the low nibble for the odd
+                let high_bits = _mm_set1_epi16(0x0880);
+                let uppercase = _mm_cmpeq_epi8(_mm_and_si128(nibbles, high_bits), high_bits);
+
+                let chunk = _mm_loadu_si128(lowercase.as_ptr().add(offset) as *const __m128i);
+                let letter = _mm_cmpgt_epi8(chunk, _mm_set1_epi8(b'9' as i8));
+                let case_bit = _mm_and_si128(_mm_and_si128(uppercase, letter), _mm_set1_epi8(0x20));
+
+                _mm_storeu_si128(
+                    lowercase.as_mut_ptr().add(offset) as *mut __m128i,
+                    _mm_andnot_si128(case_bit, chunk),
+                );
+            }
+        }
+    }
+
+    /// `0xff` for the bytes within `low..=high`, all of which must be ASCII
+    #[target_feature(enable = "sse2")]
+    fn in_range(chunk: __m128i, low: u8, high: u8) -> __m128i {
+        _mm_and_si128(
+            _mm_cmpgt_epi8(chunk, _mm_set1_epi8(low as i8 - 1)),
+            _mm_cmplt_epi8(chunk, _mm_set1_epi8(high as i8 + 1)),
+        )
+    }
+}
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+mod avx2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    /// The offsets of the 32-byte chunks, the second one overlaps the first like with SSE2
+    const OFFSETS: [usize; 2] = [0, 8];
+
+    pub(super) fn is_supported() -> bool {
+        is_x86_feature_supported!("avx2")
+    }
+
+    /// Lowercases the hex address, returns `false` on any non hex byte
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn to_lowercase(address: &[u8; 40], lowercase: &mut [u8; 40]) -> bool {
+        let mut valid = true;
+
+        for &offset in OFFSETS.iter() {
+            // SAFETY: the chunk is within the 40 bytes of both arrays
+            unsafe {
+                let chunk = _mm256_loadu_si256(address.as_ptr().add(offset) as *const __m256i);
+                let lower = _mm256_or_si256(chunk, _mm256_set1_epi8(0x20));
+                let hex = _mm256_or_si256(in_range(chunk, b'0', b'9'), in_range(lower, b'a', b'f'));
+
+                valid &= _mm256_movemask_epi8(hex) == -1;
+                _mm256_storeu_si256(lowercase.as_mut_ptr().add(offset) as *mut __m256i, lower);
+            }
+        }
+
+        valid
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn apply_hash(lowercase: &mut [u8; 40], hash: &[u8; 32]) {
+        for &offset in OFFSETS.iter() {
+            // SAFETY: the chunk is within the 40 bytes of the address
+            // and the 16 hash bytes of the chunk are within the 32 bytes of the hash
+            unsafe {
+                let hash_bytes = _mm_loadu_si128(hash.as_ptr().add(offset / 2) as *const __m128i);
+                // every hash byte twice, the unpacking of SSE2 being within the 128-bit lanes
+                let words = _mm256_cvtepu8_epi16(hash_bytes);
+                let nibbles = _mm256_or_si256(words, _mm256_slli_epi16(words, 8));
+                let high_bits = _mm256_set1_epi16(0x0880);
+                let uppercase = _mm256_cmpeq_epi8(_mm256_and_si256(nibbles, high_bits), high_bits);
+
+                let chunk = _mm256_loadu_si256(lowercase.as_ptr().add(offset) as *const __m256i);
+                let letter = _mm256_cmpgt_epi8(chunk, _mm256_set1_epi8(b'9' as i8));
+                let case_bit =
+                    _mm256_and_si256(_mm256_and_si256(uppercase, letter), _mm256_set1_epi8(0x20));
+
+                _mm256_storeu_si256(
+                    lowercase.as_mut_ptr().add(offset) as *mut __m256i,
+                    _mm256_andnot_si256(case_bit, chunk),
+                );
+            }
+        }
+    }
+
+    /// `0xff` for the bytes within `low..=high`, all of which must be ASCII
+    #[target_feature(enable = "avx2")]
+    fn in_range(chunk: __m256i, low: u8, high: u8) -> __m256i {
+        _mm256_and_si256(
+            _mm256_cmpgt_epi8(chunk, _mm256_set1_epi8(low as i8 - 1)),
+            _mm256_cmpgt_epi8(_mm256_set1_epi8(high as i8 + 1), chunk),
+        )
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod neon {
+    use core::arch::aarch64::*;
+
+    /// The offsets of the 16-byte chunks, like with SSE2
+    const OFFSETS: [usize; 3] = [0, 16, 24];
+
+    /// NEON is part of the `aarch64` baseline, but not of the soft-float targets
+    pub(super) fn is_supported() -> bool {
+        #[cfg(feature = "std")]
+        {
+            std::arch::is_aarch64_feature_detected!("neon")
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            cfg!(target_feature = "neon")
+        }
+    }
+
+    /// Lowercases the hex address, returns `false` on any non hex byte
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn to_lowercase(address: &[u8; 40], lowercase: &mut [u8; 40]) -> bool {
+        let mut valid = true;
+
+        for &offset in OFFSETS.iter() {
+            // SAFETY: the chunk is within the 40 bytes of both arrays
+            unsafe {
+                let chunk = vld1q_u8(address.as_ptr().add(offset));
+                let lower = vorrq_u8(chunk, vdupq_n_u8(0x20));
+                let hex = vorrq_u8(in_range(chunk, b'0', b'9'), in_range(lower, b'a', b'f'));
+
+                valid &= vminvq_u8(hex) == 0xff;
+                vst1q_u8(lowercase.as_mut_ptr().add(offset), lower);
+            }
+        }
+
+        valid
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn apply_hash(lowercase: &mut [u8; 40], hash: &[u8; 32]) {
+        for &offset in OFFSETS.iter() {
+            // SAFETY: the chunk is within the 40 bytes of the address
+            // and the 8 hash bytes of the chunk are within the 32 bytes of the hash
+            unsafe {
+                let hash_bytes = vld1_u8(hash.as_ptr().add(offset / 2));
+                // every hash byte twice, i.e. once per nibble
+                let nibbles = vzip1q_u8(
+                    vcombine_u8(hash_bytes, hash_bytes),
+                    vcombine_u8(hash_bytes, hash_bytes),
+                );
+                let high_bits = vreinterpretq_u8_u16(vdupq_n_u16(0x0880));
+                let uppercase = vtstq_u8(nibbles, high_bits);
+
+                let chunk = vld1q_u8(lowercase.as_ptr().add(offset));
+                let letter = vcgtq_u8(chunk, vdupq_n_u8(b'9'));
+                let case_bit = vandq_u8(vandq_u8(uppercase, letter), vdupq_n_u8(0x20));
+
+                vst1q_u8(
+                    lowercase.as_mut_ptr().add(offset),
+                    vbicq_u8(chunk, case_bit),
+                );
+            }
+        }
+    }
+
+    /// `0xff` for the bytes within `low..=high`
+    #[target_feature(enable = "neon")]
+    fn in_range(chunk: uint8x16_t, low: u8, high: u8) -> uint8x16_t {
+        vandq_u8(
+            vcgeq_u8(chunk, vdupq_n_u8(low)),
+            vcleq_u8(chunk, vdupq_n_u8(high)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keccak256;
+    extern crate test;
+    use test::Bencher;
+
+    const ADDRESS: &[u8; 40] = b"e0fC04FA2d34a66B779fd5CEe748268032a146c0";
+
+    #[test]
+    fn test_to_lowercase() {
+        let mut lowercase = [0_u8; 40];
+
+        assert_eq!(Ok(()), to_lowercase(ADDRESS, &mut lowercase));
+        assert_eq!(b"e0fc04fa2d34a66b779fd5cee748268032a146c0", &lowercase);
+
+        // every byte at every position, including the overlapping chunk
+        for i in 0..40 {
+            for byte in 0..=u8::MAX {
+                let mut address = *ADDRESS;
+                address[i] = byte;
+
+                let expected = scalar::to_lowercase(&address, &mut [0_u8; 40]);
+                assert_eq!(expected.is_ok(), byte.is_ascii_hexdigit());
+                assert_eq!(expected, to_lowercase(&address, &mut lowercase));
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_hash() {
+        let mut lowercase = [0_u8; 40];
+        to_lowercase(ADDRESS, &mut lowercase).expect("Should be valid hex");
+
+        for seed in 0..200_u32 {
+            let hash = keccak256(&seed.to_le_bytes());
+
+            let mut expected = lowercase;
+            scalar::apply_hash(&mut expected, &hash);
+
+            let mut actual = lowercase;
+            apply_hash(&mut actual, &hash);
+            assert_eq!(expected, actual, "seed {}", seed);
+        }
+    }
+
+    /// Compares a SIMD path with the scalar one, which the tests above only do for the
+    /// dispatched path
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    fn assert_simd_path(
+        to_lowercase: impl Fn(&[u8; 40], &mut [u8; 40]) -> bool,
+        apply_hash: impl Fn(&mut [u8; 40], &[u8; 32]),
+    ) {
+        for i in 0..40 {
+            for byte in 0..=u8::MAX {
+                let mut address = *ADDRESS;
+                address[i] = byte;
+
+                let mut expected = [0_u8; 40];
+                let valid = scalar::to_lowercase(&address, &mut expected).is_ok();
+                let mut lowercase = [0_u8; 40];
+                assert_eq!(valid, to_lowercase(&address, &mut lowercase));
+                if valid {
+                    assert_eq!(expected, lowercase);
+                }
+            }
+        }
+
+        let mut lowercase = [0_u8; 40];
+        scalar::to_lowercase(ADDRESS, &mut lowercase).expect("Should be valid hex");
+        for seed in 0..200_u32 {
+            let hash = keccak256(&seed.to_le_bytes());
+
+            let mut expected = lowercase;
+            scalar::apply_hash(&mut expected, &hash);
+
+            let mut actual = lowercase;
+            apply_hash(&mut actual, &hash);
+            assert_eq!(expected, actual, "seed {}", seed);
+        }
+    }
+
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn test_x86_paths() {
+        // SAFETY: the target features are supported
+        if sse2::is_supported() {
+            assert_simd_path(
+                |address, lowercase| unsafe { sse2::to_lowercase(address, lowercase) },
+                |lowercase, hash| unsafe { sse2::apply_hash(lowercase, hash) },
+            );
+        }
+        if avx2::is_supported() {
+            assert_simd_path(
+                |address, lowercase| unsafe { avx2::to_lowercase(address, lowercase) },
+                |lowercase, hash| unsafe { avx2::apply_hash(lowercase, hash) },
+            );
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    #[test]
+    fn test_neon_path() {
+        // SAFETY: the target feature is supported
+        if neon::is_supported() {
+            assert_simd_path(
+                |address, lowercase| unsafe { neon::to_lowercase(address, lowercase) },
+                |lowercase, hash| unsafe { neon::apply_hash(lowercase, hash) },
+            );
+        }
+    }
+
+    #[bench]
+    fn bench_to_lowercase_scalar(b: &mut Bencher) {
+        let mut lowercase = [0_u8; 40];
+
+        b.iter(|| {
+            for _ in 0..20_000 {
+                scalar::to_lowercase(test::black_box(ADDRESS), &mut lowercase).unwrap();
+                test::black_box(&lowercase);
+            }
+        })
+    }
+
+    #[bench]
+    fn bench_to_lowercase(b: &mut Bencher) {
+        let mut lowercase = [0_u8; 40];
+
+        b.iter(|| {
+            for _ in 0..20_000 {
+                to_lowercase(test::black_box(ADDRESS), &mut lowercase).unwrap();
+                test::black_box(&lowercase);
+            }
+        })
+    }
+
+    #[bench]
+    fn bench_apply_hash_scalar(b: &mut Bencher) {
+        let hash = keccak256(ADDRESS);
+        let mut lowercase = [0_u8; 40];
+        to_lowercase(ADDRESS, &mut lowercase).unwrap();
+
+        b.iter(|| {
+            for _ in 0..20_000 {
+                let mut checksummed = lowercase;
+                scalar::apply_hash(&mut checksummed, test::black_box(&hash));
+                test::black_box(&checksummed);
+            }
+        })
+    }
+
+    #[bench]
+    fn bench_apply_hash(b: &mut Bencher) {
+        let hash = keccak256(ADDRESS);
+        let mut lowercase = [0_u8; 40];
+        to_lowercase(ADDRESS, &mut lowercase).unwrap();
+
+        b.iter(|| {
+            for _ in 0..20_000 {
+                let mut checksummed = lowercase;
+                apply_hash(&mut checksummed, test::black_box(&hash));
+                test::black_box(&checksummed);
+            }
+        })
+    }
+}
@@ -0,0 +1,263 @@
+//! The Keccak-256 backends used for the checksums.
+//!
+//! The backend is selected with the cargo features, in order of preference:
+//!
+//! - `tiny-keccak` (default) - [`TinyKeccak`]
+//! - `sha3` - [`Sha3Keccak`], to share the RustCrypto implementation with the rest of a binary
+//! - none of them - [`PortableKeccak`], the crate's own implementation
+//!
+//! [`DefaultKeccak`] is the selected backend. Any other implementation of the [`Keccak256`]
+//! trait can be used with [`Checksum::with_hasher`](crate::Checksum::with_hasher).
+//!
+//! The same hashing is available through [`keccak256`] and [`Keccak256Hasher`], e.g. for function
+//! selectors, event topics or storage slots.
+use crate::const_keccak::{self, RATE};
+
+/// An incremental Keccak-256 hasher
+pub trait Keccak256: Default {
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> [u8; 32];
+}
+
+/// The backend selected with the cargo features
+#[cfg(feature = "tiny-keccak")]
+pub type DefaultKeccak = TinyKeccak;
+/// The backend selected with the cargo features
+#[cfg(all(feature = "sha3", not(feature = "tiny-keccak")))]
+pub type DefaultKeccak = Sha3Keccak;
+/// The backend selected with the cargo features
+#[cfg(not(any(feature = "tiny-keccak", feature = "sha3")))]
+pub type DefaultKeccak = PortableKeccak;
+
+/// The Keccak-256 hash of the data, using the [`DefaultKeccak`].
+///
+/// ```
+/// use eth_checksum::keccak256;
+///
+/// // the `transfer(address,uint256)` function selector
+/// let hash = keccak256(b"transfer(address,uint256)");
+/// assert_eq!([0xa9, 0x05, 0x9c, 0xbb], hash[..4]);
+/// ```
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256Hasher::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// An incremental Keccak-256, using the [`DefaultKeccak`] unless another backend is given.
+///
+/// ```
+/// use eth_checksum::{keccak256, Keccak256Hasher};
+///
+/// let mut hasher = Keccak256Hasher::new();
+/// hasher.update(b"Transfer(");
+/// hasher.update(b"address,address,uint256)");
+///
+/// assert_eq!(keccak256(b"Transfer(address,address,uint256)"), hasher.finalize());
+/// ```
+#[derive(Clone, Default)]
+pub struct Keccak256Hasher<H = DefaultKeccak>(H);
+
+impl Keccak256Hasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<H: Keccak256> Keccak256Hasher<H> {
+    /// The hasher with the given backend
+    pub fn with_backend() -> Self {
+        Self(H::default())
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize()
+    }
+}
+
+/// The [`tiny-keccak`](https://docs.rs/tiny-keccak) backend, enabled with the `tiny-keccak` feature
+#[cfg(feature = "tiny-keccak")]
+#[derive(Clone)]
+pub struct TinyKeccak(tiny_keccak::Keccak);
+
+#[cfg(feature = "tiny-keccak")]
+impl Default for TinyKeccak {
+    fn default() -> Self {
+        Self(tiny_keccak::Keccak::v256())
+    }
+}
+
+#[cfg(feature = "tiny-keccak")]
+impl Keccak256 for TinyKeccak {
+    fn update(&mut self, data: &[u8]) {
+        tiny_keccak::Hasher::update(&mut self.0, data)
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        let mut hash = [0_u8; 32];
+        tiny_keccak::Hasher::finalize(self.0, &mut hash);
+        hash
+    }
+}
+
+/// The [`sha3`](https://docs.rs/sha3) backend, enabled with the `sha3` feature
+#[cfg(feature = "sha3")]
+#[derive(Clone, Default)]
+pub struct Sha3Keccak(sha3::Keccak256);
+
+#[cfg(feature = "sha3")]
+impl Keccak256 for Sha3Keccak {
+    fn update(&mut self, data: &[u8]) {
+        sha3::Digest::update(&mut self.0, data)
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        sha3::Digest::finalize(self.0).into()
+    }
+}
+
+/// The crate's own backend, always available and used when no other backend is enabled
+#[derive(Clone)]
+pub struct PortableKeccak {
+    state: [u64; 25],
+    buffer: [u8; RATE],
+    /// The bytes in the buffer, always less than `RATE`
+    buffered: usize,
+}
+
+impl Default for PortableKeccak {
+    fn default() -> Self {
+        Self {
+            state: [0; 25],
+            buffer: [0; RATE],
+            buffered: 0,
+        }
+    }
+}
+
+impl Keccak256 for PortableKeccak {
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let length = data.len().min(RATE - self.buffered);
+            self.buffer[self.buffered..self.buffered + length].copy_from_slice(&data[..length]);
+            self.buffered += length;
+            data = &data[length..];
+
+            if self.buffered == RATE {
+                self.state = const_keccak::absorb(self.state, &self.buffer, 0);
+                self.buffered = 0;
+            }
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        self.buffer[self.buffered..]
+            .iter_mut()
+            .for_each(|byte| *byte = 0);
+
+        const_keccak::finalize(self.state, self.buffer, self.buffered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::EIP_55_CASES, Checksum};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn assert_eip_55_cases<H: Keccak256>() {
+        let checksummer = Checksum::with_hasher::<H>();
+
+        for address in EIP_55_CASES.iter() {
+            let checksummed = checksummer
+                .encode(&address.to_lowercase())
+                .expect("Should be valid address");
+
+            assert_eq!(&checksummed, address);
+        }
+    }
+
+    #[test]
+    fn test_backends_eip_55_cases() {
+        assert_eip_55_cases::<PortableKeccak>();
+        #[cfg(feature = "tiny-keccak")]
+        assert_eip_55_cases::<TinyKeccak>();
+        #[cfg(feature = "sha3")]
+        assert_eip_55_cases::<Sha3Keccak>();
+    }
+
+    #[test]
+    fn test_keccak256() {
+        // the `Transfer(address,address,uint256)` event topic
+        let expected = [
+            0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37,
+            0x8d, 0xaa, 0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d,
+            0xf5, 0x23, 0xb3, 0xef,
+        ];
+        let signature = b"Transfer(address,address,uint256)";
+        assert_eq!(expected, keccak256(signature));
+
+        let mut hasher = Keccak256Hasher::<PortableKeccak>::with_backend();
+        signature.chunks(5).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(expected, hasher.finalize());
+    }
+
+    #[test]
+    fn test_portable_keccak_incremental_updates() {
+        // around the block boundaries
+        let data = (0..600).map(|i| (i * 7 + 3) as u8).collect::<Vec<u8>>();
+
+        for chunk_size in [1, 7, 135, 136, 137, 600].iter() {
+            let mut hasher = PortableKeccak::default();
+            data.chunks(*chunk_size)
+                .for_each(|chunk| hasher.update(chunk));
+
+            assert_eq!(
+                const_keccak::keccak256(&data),
+                hasher.finalize(),
+                "chunk size {}",
+                chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn test_counting_hasher() {
+        static HASHES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Default)]
+        struct CountingKeccak(DefaultKeccak);
+
+        impl Keccak256 for CountingKeccak {
+            fn update(&mut self, data: &[u8]) {
+                self.0.update(data)
+            }
+
+            fn finalize(self) -> [u8; 32] {
+                HASHES.fetch_add(1, Ordering::SeqCst);
+                self.0.finalize()
+            }
+        }
+
+        let checksummer = Checksum::with_hasher::<CountingKeccak>();
+        assert_eq!(
+            checksummer
+                .encode("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+                .expect("Should be valid address"),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+        assert_eq!(1, HASHES.load(Ordering::SeqCst));
+
+        // fails before hashing
+        assert!(checksummer.verify("0xq").is_err());
+        assert!(checksummer
+            .verify("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeq")
+            .is_err());
+        assert_eq!(1, HASHES.load(Ordering::SeqCst));
+    }
+}
@@ -1,148 +1,440 @@
 #![deny(clippy::all)]
 #![deny(rust_2018_idioms)]
 #![cfg_attr(any(test, bench), feature(test))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{convert::TryInto, fmt, marker::PhantomData};
 
 static PREFIX: &str = "0x";
 
-pub use error::Error;
+pub use address::Address;
+pub use batch::{ChecksumIterator, Checksummed};
+pub use checksummed::ChecksummedAddress;
+#[cfg(feature = "alloc")]
+pub use error::OwnedError;
+pub use error::{Error, KeyError};
+pub use keccak::{keccak256, DefaultKeccak, Keccak256, Keccak256Hasher};
+pub use options::ParseOptions;
+pub use render::{InputError, Rendered};
+#[cfg(feature = "k256")]
+pub use signature::{hash_message, verify_signer, SignatureError, SignerError};
+#[cfg(feature = "alloc")]
+pub use suggest::Correction;
 pub use try_checksum::*;
+pub use verification::*;
+
+mod address;
+mod batch;
+mod checksummed;
+mod const_keccak;
+#[cfg(feature = "alloc")]
+mod diagnose;
+mod error;
+#[cfg(feature = "k256")]
+pub mod hd;
+mod hex;
+pub mod keccak;
+#[cfg(feature = "macros")]
+mod macros;
+mod options;
+mod render;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "k256")]
+mod signature;
+#[cfg(feature = "alloc")]
+mod suggest;
+mod try_checksum;
+pub mod unicode;
 
 pub struct Checksum {}
 
 impl Checksum {
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(input: &str) -> Result<String, Error<'_>> {
-        match input.len() {
-            40 => to_checksum_address(input),
-            42 => {
-                let prefix = &input[..2];
+        <Checksummer>::default().from_str(input)
+    }
+
+    /// Checksums a (prefixed or not) address without allocating.
+    ///
+    /// The returned [`ChecksummedAddress`] is always prefixed with `0x`.
+    pub fn encode(input: &str) -> Result<ChecksummedAddress, Error<'_>> {
+        <Checksummer>::default().encode(input)
+    }
 
-                if &prefix != &PREFIX {
-                    return Err(Error::Prefix {
-                        expected: PREFIX,
-                        actual: prefix,
-                    });
-                }
+    /// Checksums a (prefixed or not) address into the buffer without allocating.
+    ///
+    /// The returned `str` is always prefixed with `0x`.
+    pub fn encode_to_slice<'a, 'b>(
+        input: &'a str,
+        buffer: &'b mut [u8; 42],
+    ) -> Result<&'b str, Error<'a>> {
+        <Checksummer>::default().encode_to_slice(input, buffer)
+    }
 
-                let hash = &input[2..];
+    /// Verifies the EIP-55 checksum of a (prefixed or not) address.
+    ///
+    /// All lowercase and all uppercase addresses carry no checksum and are reported as such,
+    /// while mixed-case addresses are checked against the checksummed casing.
+    pub fn verify(input: &str) -> Result<Verification, Error<'_>> {
+        <Checksummer>::default().verify(input)
+    }
 
-                let checksummed = to_checksum_address(hash)?;
+    /// Same as [`Checksum::from_str`], but rejects mixed-case addresses with an invalid checksum
+    /// with [`Error::Checksum`].
+    #[cfg(feature = "alloc")]
+    pub fn validate(input: &str) -> Result<String, Error<'_>> {
+        <Checksummer>::default().validate(input)
+    }
 
-                Ok(format!("{}{}", prefix, checksummed))
-            }
-            actual => Err(Error::Length {
-                expected_either: [40, 42],
-                actual,
-            }),
+    /// Chain specific checksums as defined in [EIP-1191](https://eips.ethereum.org/EIPS/eip-1191),
+    /// used by RSK (chain id `30`) and a few other chains.
+    pub fn with_chain_id(chain_id: u64) -> Checksummer {
+        <Checksummer>::default().with_chain_id(chain_id)
+    }
+
+    /// Parses the input as per the options, e.g. to accept user input with whitespace or quotes.
+    pub fn with_options(options: ParseOptions) -> Checksummer {
+        <Checksummer>::default().with_options(options)
+    }
+
+    /// Checksums with a custom Keccak-256 implementation instead of the [`DefaultKeccak`],
+    /// e.g. to share the one already used by the rest of the binary.
+    pub fn with_hasher<H: Keccak256>() -> Checksummer<H> {
+        Checksummer::default()
+    }
+}
+
+/// Checksums addresses with an optional EIP-1191 chain id, see [`Checksum::with_chain_id`],
+/// parsing the input as per the [`ParseOptions`], see [`Checksum::with_options`],
+/// and hashing with the `H` Keccak-256 implementation, see [`Checksum::with_hasher`].
+///
+/// The default `Checksummer` has no chain id, uses plain EIP-55 checksums
+/// and the default [`ParseOptions`].
+pub struct Checksummer<H = DefaultKeccak> {
+    chain_id: Option<u64>,
+    options: ParseOptions,
+    hasher: PhantomData<fn() -> H>,
+}
+
+impl<H> Default for Checksummer<H> {
+    fn default() -> Self {
+        Self {
+            chain_id: None,
+            options: ParseOptions::default(),
+            hasher: PhantomData,
         }
     }
 }
 
-mod error {
-    use std::str::Utf8Error;
+impl<H> Clone for Checksummer<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H> Copy for Checksummer<H> {}
+
+impl<H> PartialEq for Checksummer<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.chain_id == other.chain_id && self.options == other.options
+    }
+}
+
+impl<H> Eq for Checksummer<H> {}
+
+impl<H> fmt::Debug for Checksummer<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checksummer")
+            .field("chain_id", &self.chain_id)
+            .field("options", &self.options)
+            .field("hasher", &core::any::type_name::<H>())
+            .finish()
+    }
+}
+
+impl<H: Keccak256> Checksummer<H> {
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    /// The same checksummer with the EIP-1191 chain id, see [`Checksum::with_chain_id`]
+    pub fn with_chain_id(self, chain_id: u64) -> Self {
+        Self {
+            chain_id: Some(chain_id),
+            ..self
+        }
+    }
+
+    pub fn options(&self) -> ParseOptions {
+        self.options
+    }
+
+    /// The same checksummer with the parse options, see [`Checksum::with_options`]
+    pub fn with_options(self, options: ParseOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// See [`Checksum::from_str`]
+    #[cfg(feature = "alloc")]
+    pub fn from_str<'a>(&self, input: &'a str) -> Result<String, Error<'a>> {
+        let (prefix, checksummed, _) = self.checksum(input)?;
+
+        Ok(prefix_address(prefix, ascii_str(&checksummed)))
+    }
+
+    /// See [`Checksum::encode`]
+    pub fn encode<'a>(&self, input: &'a str) -> Result<ChecksummedAddress, Error<'a>> {
+        let mut buffer = [0_u8; 42];
+        self.encode_to_slice(input, &mut buffer)?;
+
+        Ok(ChecksummedAddress::from_buffer(buffer))
+    }
+
+    /// See [`Checksum::encode_to_slice`]
+    pub fn encode_to_slice<'a, 'b>(
+        &self,
+        input: &'a str,
+        buffer: &'b mut [u8; 42],
+    ) -> Result<&'b str, Error<'a>> {
+        let (_, checksummed, _) = self.checksum(input)?;
+
+        buffer[..2].copy_from_slice(PREFIX.as_bytes());
+        buffer[2..].copy_from_slice(&checksummed);
+
+        Ok(ascii_str(buffer))
+    }
 
-    #[derive(Debug, PartialEq, Eq)]
-    pub enum Error<'a> {
-        Length {
-            expected_either: [usize; 2],
-            actual: usize,
-        },
-        Prefix {
-            expected: &'a str,
-            actual: &'a str,
-        },
-        Utf8(Utf8Error),
-        /// Invalid Hex character
-        HexChar {
-            value: char,
-            index: usize,
-        },
+    /// See [`Checksum::verify`]
+    ///
+    /// The addresses rejected by the checksum options are reported as errors.
+    pub fn verify<'a>(&self, input: &'a str) -> Result<Verification, Error<'a>> {
+        self.checksum(input)
+            .map(|(_, _, verification)| verification)
     }
 
-    impl<'a> From<Utf8Error> for Error<'a> {
-        fn from(e: Utf8Error) -> Self {
-            Self::Utf8(e)
+    /// See [`Checksum::validate`]
+    #[cfg(feature = "alloc")]
+    pub fn validate<'a>(&self, input: &'a str) -> Result<String, Error<'a>> {
+        match self.checksum(input)? {
+            (_, _, Verification::Invalid(positions)) => Err(Error::Checksum { positions }),
+            (prefix, checksummed, _) => Ok(prefix_address(prefix, ascii_str(&checksummed))),
         }
     }
+
+    /// The EIP-55 (or EIP-1191 with a chain id) checksummed and prefixed address
+    pub fn encode_address(&self, address: &Address) -> ChecksummedAddress {
+        let mut buffer = [0_u8; 42];
+        buffer[..2].copy_from_slice(PREFIX.as_bytes());
+
+        let hex: &mut [u8; 40] = (&mut buffer[2..])
+            .try_into()
+            .expect("Buffer has 40 bytes after the prefix");
+        address.write_lowercase_hex(hex);
+        apply_checksum::<H>(hex, self.chain_id);
+
+        ChecksummedAddress::from_buffer(buffer)
+    }
+
+    /// Splits the prefix and checksums the address of the input as per the options
+    fn checksum<'a>(
+        &self,
+        input: &'a str,
+    ) -> Result<(Option<&'static str>, [u8; 40], Verification), Error<'a>> {
+        self.options.normalized(input, |input| {
+            let (prefix, address) = self.options.split_trimmed_prefix(input)?;
+
+            let (checksummed, verification) = verify_checksum::<H>(address, self.chain_id)
+                .map_err(|error| error.after_prefix(prefix))?;
+            self.options.check_checksum(address, verification)?;
+
+            Ok((prefix, checksummed, verification))
+        })
+    }
 }
 
-mod try_checksum {
-    use super::*;
+/// Splits the optional `0x` prefix from the address as per the default [`ParseOptions`]
+fn split_prefix(input: &str) -> Result<(Option<&'static str>, &str), Error<'_>> {
+    ParseOptions::default().split_prefix(input)
+}
+
+#[cfg(feature = "alloc")]
+fn prefix_address(prefix: Option<&str>, checksummed: &str) -> String {
+    let prefix = prefix.unwrap_or_default();
+
+    let mut address = String::with_capacity(prefix.len() + checksummed.len());
+    address.push_str(prefix);
+    address.push_str(checksummed);
+    address
+}
 
-    pub trait TryChecksum {
-        fn try_checksum<'a>(&'a self) -> Result<String, Error<'a>>;
+/// The checksum and hex functions only ever write ASCII
+fn ascii_str(bytes: &[u8]) -> &str {
+    core::str::from_utf8(bytes).expect("Should always be ASCII")
+}
+
+mod verification {
+    use core::fmt;
+
+    /// The outcome of verifying the EIP-55 checksum of an address
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Verification {
+        /// Mixed-case address with a valid checksum
+        Valid,
+        /// All lowercase address (or one without any letters), i.e. no checksum present
+        Lowercase,
+        /// All uppercase address, i.e. no checksum present
+        Uppercase,
+        /// Mixed-case address with an invalid checksum
+        Invalid(Positions),
     }
 
-    impl TryChecksum for str {
-        fn try_checksum<'a>(&'a self) -> Result<String, Error<'a>> {
-            Checksum::from_str(self)
+    impl Verification {
+        /// Whether the address carries a checksum, regardless of it being valid or not
+        pub fn has_checksum(&self) -> bool {
+            match self {
+                Verification::Valid | Verification::Invalid(_) => true,
+                Verification::Lowercase | Verification::Uppercase => false,
+            }
         }
     }
 
-    impl TryChecksum for String {
-        fn try_checksum<'a>(&'a self) -> Result<String, Error<'a>> {
-            Checksum::from_str(self)
+    /// A set of nibble positions (`0..40`) in the hex part of an address, i.e. without the prefix
+    #[derive(Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Positions(u64);
+
+    impl Positions {
+        /// Collects the positions at which the two hex addresses differ
+        pub(crate) fn mismatches(address: &[u8], checksummed: &[u8]) -> Self {
+            address
+                .iter()
+                .zip(checksummed.iter())
+                .enumerate()
+                .filter(|(_, (actual, expected))| actual != expected)
+                .fold(Self::default(), |mut positions, (i, _)| {
+                    positions.insert(i);
+                    positions
+                })
+        }
+
+        pub(crate) fn insert(&mut self, index: usize) {
+            self.0 |= 1 << index;
+        }
+
+        pub fn contains(&self, index: usize) -> bool {
+            index < 64 && self.0 & (1 << index) != 0
+        }
+
+        pub fn len(&self) -> usize {
+            self.0.count_ones() as usize
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.0 == 0
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+            (0..64).filter(move |&i| self.contains(i))
         }
     }
 
-    impl TryChecksum for [u8; 40] {
-        fn try_checksum<'a>(&'a self) -> Result<String, Error<'a>> {
-            let string = std::str::from_utf8(self)?;
-            Checksum::from_str(string)
+    impl fmt::Debug for Positions {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_set().entries(self.iter()).finish()
         }
     }
 }
 
-fn to_checksum_address(address_string: &str) -> Result<String, Error<'_>> {
-    let address_string = address_string.to_lowercase();
-    let hash = keccak256_hash(&address_string);
+/// Checksums the hex address (without prefix) into `checksummed` without allocating,
+/// as per EIP-55, or EIP-1191 when a chain id is given
+fn to_checksum_address<'a, H: Keccak256>(
+    address_string: &'a str,
+    chain_id: Option<u64>,
+    checksummed: &mut [u8; 40],
+) -> Result<(), Error<'a>> {
+    let address: &[u8; 40] = address_string
+        .as_bytes()
+        .try_into()
+        .expect("The prefix is split from a 40 or 42 bytes input");
 
-    address_string
-        .char_indices()
-        .try_fold(String::with_capacity(40), |mut result, (i, a_char)| {
-            let new_char = match a_char {
-                a_char @ '0'..='9' => a_char,
-                a_char @ 'a'..='f' => {
-                    if should_be_uppercased(&hash, i) {
-                        a_char.to_uppercase().next().unwrap()
-                    } else {
-                        a_char
-                    }
-                }
-                a_char => {
-                    // fail as soon as possible
-                    // On the first invalid char
-                    return Err(Error::HexChar {
-                        value: a_char,
-                        index: i,
-                    });
-                }
-            };
+    // all the bytes before the invalid one are ASCII, i.e. it's on a char boundary
+    hex::to_lowercase(address, checksummed).map_err(|i| invalid_hex_char(address_string, i))?;
 
-            result.push(new_char);
-            Ok(result)
-        })
+    apply_checksum::<H>(checksummed, chain_id);
+
+    Ok(())
+}
+
+/// Uppercases the letters of the lowercase hex address as per the checksum
+fn apply_checksum<H: Keccak256>(lowercase: &mut [u8; 40], chain_id: Option<u64>) {
+    let mut hasher = Keccak256Hasher::<H>::with_backend();
+    // EIP-1191 prefixes the pre-image with the chain id and the prefixed address
+    if let Some(chain_id) = chain_id {
+        hasher.update(decimal(chain_id, &mut [0_u8; 20]));
+        hasher.update(PREFIX.as_bytes());
+    }
+    hasher.update(&lowercase[..]);
+
+    hex::apply_hash(lowercase, &hasher.finalize());
 }
 
-fn keccak256_hash<T: AsRef<[u8]>>(address: T) -> [u8; 40] {
-    use tiny_keccak::{Hasher, Keccak};
+/// The `HexChar` error for the byte at `index`, all the bytes before it must be ASCII
+fn invalid_hex_char(address_string: &str, index: usize) -> Error<'_> {
+    let value = address_string[index..]
+        .chars()
+        .next()
+        .expect("Index is within the address");
 
-    let mut hasher = Keccak::v256();
+    Error::HexChar { value, index }
+}
+
+/// Writes the decimal digits of `number` at the end of the buffer
+fn decimal(mut number: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
 
-    let mut result: [u8; 40] = [0_u8; 40];
-    hasher.update(address.as_ref());
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (number % 10) as u8;
+        number /= 10;
 
-    hasher.finalize(&mut result);
-    result
+        if number == 0 {
+            break &buffer[start..];
+        }
+    }
 }
 
-fn should_be_uppercased(array: &[u8; 40], i: usize) -> bool {
-    let half_byte_at: u8 = if i & 1 == 0 {
-        array[i / 2] >> 4
-    } else {
-        array[i / 2] & 0x0f
+/// Checksums the address and compares it against the casing of the input
+fn verify_checksum<H: Keccak256>(
+    address_string: &str,
+    chain_id: Option<u64>,
+) -> Result<([u8; 40], Verification), Error<'_>> {
+    let mut checksummed = [0_u8; 40];
+    to_checksum_address::<H>(address_string, chain_id, &mut checksummed)?;
+
+    let has_lowercase = address_string.bytes().any(|b| b.is_ascii_lowercase());
+    let has_uppercase = address_string.bytes().any(|b| b.is_ascii_uppercase());
+
+    let verification = match (has_lowercase, has_uppercase) {
+        (true, true) => {
+            let positions = Positions::mismatches(address_string.as_bytes(), &checksummed);
+
+            if positions.is_empty() {
+                Verification::Valid
+            } else {
+                Verification::Invalid(positions)
+            }
+        }
+        (false, true) => Verification::Uppercase,
+        _ => Verification::Lowercase,
     };
 
-    half_byte_at >= 8
+    Ok((checksummed, verification))
 }
 
 #[cfg(test)]
@@ -151,48 +443,136 @@ mod tests {
     extern crate test;
     use test::Bencher;
 
+    /// The checksummed address of the EIP-55 examples
+    pub(crate) const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    /// See EIP-55: https://github.com/ethereum/EIPs/blob/master/EIPS/eip-55.md#test-cases
+    pub(crate) const EIP_55_CASES: [&str; 8] = [
+        // All caps:
+        "0x52908400098527886E0F7030069857D2E4169EE7",
+        "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+        // All lower
+        "0xde709f2102306220921060314715629080e2fb77",
+        "0x27b1fdb04752bbc536007a920d24acb045561c26",
+        // Normal:
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    /// Decodes the (unprefixed) hex of a test vector
+    pub(crate) fn hex_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("Should be hex"))
+            .collect()
+    }
+
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_checksum_from_str() {
         let prefixed_checksum = "0xe0FC04FA2d34a66B779fd5CEe748268032a146c0";
 
         let addr_lowercase = "0xe0fc04fa2d34a66b779fd5cee748268032a146c0";
-        let checksummed = Checksum::from_str(&addr_lowercase).expect("Should be valid String!");
+        let checksummed = Checksum::from_str(addr_lowercase).expect("Should be valid String!");
 
         assert_eq!(PREFIX, &checksummed[..2]);
         assert_eq!(checksummed, prefixed_checksum);
 
         let addr_uppercase = "0xE0FC04FA2D34A66B779FD5CEE748268032A146C0";
-        let checksummed = Checksum::from_str(&addr_uppercase).expect("Should be valid String!");
+        let checksummed = Checksum::from_str(addr_uppercase).expect("Should be valid String!");
 
         assert_eq!(PREFIX, &checksummed[..2]);
         assert_eq!(checksummed, prefixed_checksum);
     }
 
     #[test]
-    /// See EIP-55: https://github.com/ethereum/EIPs/blob/master/EIPS/eip-55.md#test-cases
+    #[cfg(feature = "alloc")]
     fn test_checksum_from_str_eip_55_cases() {
-        let cases = [
-            // All caps:
-            "0x52908400098527886E0F7030069857D2E4169EE7",
-            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
-            // All lower
-            "0xde709f2102306220921060314715629080e2fb77",
-            "0x27b1fdb04752bbc536007a920d24acb045561c26",
-            // Normal:
-            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
-            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
-            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
-            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        for address in EIP_55_CASES.iter() {
+            let checksummed = Checksum::from_str(address).expect("Should be valid String!");
+
+            assert_eq!(&checksummed, address);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// See EIP-1191: https://eips.ethereum.org/EIPS/eip-1191#test-cases
+    fn test_checksum_with_chain_id_eip_1191_cases() {
+        let rsk_mainnet = [
+            "0x27b1FdB04752BBc536007A920D24ACB045561c26",
+            "0x3599689E6292B81B2D85451025146515070129Bb",
+            "0x42712D45473476B98452f434E72461577d686318",
+            "0x52908400098527886E0F7030069857D2E4169ee7",
+            "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+            "0x6549F4939460DE12611948B3F82B88C3C8975323",
+            "0x66F9664f97f2B50F62d13EA064982F936de76657",
+            "0x8617E340b3D01Fa5f11f306f4090fd50E238070D",
+            "0x88021160c5C792225E4E5452585947470010289d",
+            "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+            "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+            "0xDe709F2102306220921060314715629080e2FB77",
+            "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+        ];
+        let rsk_testnet = [
+            "0x27B1FdB04752BbC536007a920D24acB045561C26",
+            "0x3599689e6292b81b2D85451025146515070129Bb",
+            "0x42712D45473476B98452F434E72461577D686318",
+            "0x52908400098527886E0F7030069857D2e4169EE7",
+            "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+            "0x6549f4939460dE12611948b3f82b88C3c8975323",
+            "0x66f9664F97F2b50f62d13eA064982F936DE76657",
+            "0x8617e340b3D01fa5F11f306F4090Fd50e238070d",
+            "0x88021160c5C792225E4E5452585947470010289d",
+            "0xd1220a0CF47c7B9Be7A2E6Ba89f429762E7b9adB",
+            "0xdbF03B407C01E7cd3cbEa99509D93f8dDDc8C6fB",
+            "0xDE709F2102306220921060314715629080e2Fb77",
+            "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359",
         ];
 
-        for address in cases.iter() {
-            let checksummed = Checksum::from_str(&address).expect("Should be valid String!");
+        for (chain_id, cases) in [(30, &rsk_mainnet), (31, &rsk_testnet)].iter() {
+            let checksum = Checksum::with_chain_id(*chain_id);
 
-            assert_eq!(&checksummed, address);
+            for address in cases.iter() {
+                let lowercase = address.to_lowercase();
+                let checksummed = checksum
+                    .from_str(&lowercase)
+                    .expect("Should be valid String!");
+
+                assert_eq!(&checksummed, address);
+                assert_eq!(Ok(address.to_string()), checksum.validate(address));
+            }
+        }
+
+        // a valid EIP-55 checksum is not a valid EIP-1191 checksum
+        match Checksum::with_chain_id(30).validate(CHECKSUMMED) {
+            Err(Error::Checksum { .. }) => {}
+            other => panic!("Expected checksum error, got {:?}", other),
         }
     }
 
     #[test]
+    fn test_encode_to_slice() {
+        let mut buffer = [0_u8; 42];
+
+        let checksummed =
+            Checksum::encode_to_slice("e0fc04fa2d34a66b779fd5cee748268032a146c0", &mut buffer)
+                .expect("Should be valid address");
+        assert_eq!("0xe0FC04FA2d34a66B779fd5CEe748268032a146c0", checksummed);
+
+        let checksummed = Checksum::with_chain_id(30)
+            .encode_to_slice("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", &mut buffer)
+            .expect("Should be valid address");
+        assert_eq!("0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD", checksummed);
+
+        let address = Address::parse_lenient(checksummed).expect("Should be valid address");
+        assert_eq!(CHECKSUMMED, address.encode_to_slice(&mut buffer));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn test_invalid_hex_char() {
         let hex_char = "eqfc04fa2d34a66b779fd5cee748268032a146c0";
 
@@ -203,7 +583,85 @@ mod tests {
         assert_eq!(Err(expected_err), Checksum::from_str(hex_char));
     }
 
+    #[test]
+    fn test_verify_checksum() {
+        assert_eq!(Ok(Verification::Valid), Checksum::verify(CHECKSUMMED));
+        assert_eq!(Ok(Verification::Valid), CHECKSUMMED.try_verify());
+
+        let lowercase = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        assert_eq!(Ok(Verification::Lowercase), Checksum::verify(lowercase));
+
+        let uppercase = "5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED";
+        assert_eq!(Ok(Verification::Uppercase), Checksum::verify(uppercase));
+
+        // `A` at index 2 and `e` at index 39 are wrongly cased
+        let invalid = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        let verification = Checksum::verify(invalid).expect("Should be valid hex");
+
+        match verification {
+            Verification::Invalid(positions) => {
+                assert_eq!(vec![2, 39], positions.iter().collect::<Vec<_>>());
+            }
+            other => panic!("Expected invalid checksum, got {:?}", other),
+        }
+        assert!(verification.has_checksum());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_validate_rejects_invalid_checksum() {
+        let invalid = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+
+        match Checksum::validate(invalid) {
+            Err(Error::Checksum { positions }) => assert_eq!(2, positions.len()),
+            other => panic!("Expected checksum error, got {:?}", other),
+        }
+
+        let lowercase = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        assert_eq!(Ok(CHECKSUMMED.to_string()), Checksum::validate(lowercase));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_non_ascii_input_does_not_panic() {
+        // 42 bytes, with a multi-byte second character
+        let multi_byte_prefix = "0\u{e9}e0fc04fa2d34a66b779fd5cee748268032a146c";
+        assert_eq!(42, multi_byte_prefix.len());
+        assert_eq!(
+            Err(Error::Prefix {
+                expected: PREFIX,
+                actual: "0\u{e9}",
+            }),
+            Checksum::from_str(multi_byte_prefix)
+        );
+
+        // `'\u{130}'.to_lowercase()` is longer than the character itself
+        let lowercase_changes_length = "0xe0fc04fa2d34a66b779fd5cee748268032a146\u{130}";
+        assert_eq!(42, lowercase_changes_length.len());
+        assert_eq!(
+            Err(Error::HexChar {
+                value: '\u{130}',
+                index: 40,
+            }),
+            Checksum::from_str(lowercase_changes_length)
+        );
+
+        let inputs = [
+            "\u{1F600}".repeat(10),
+            "0x".to_string() + &"\u{e9}".repeat(20),
+            "\u{10FFFF}\u{10FFFF}e0fc04fa2d34a66b779fd5cee748268032a1".to_string(),
+            "\u{0}".repeat(42),
+        ];
+        for input in inputs.iter() {
+            assert!(Checksum::from_str(input).is_err());
+            assert!(Checksum::verify(input).is_err());
+            assert!(Address::parse_lenient(input).is_err());
+            assert!(input.try_checksum().is_err());
+        }
+    }
+
     #[bench]
+    #[cfg(feature = "alloc")]
     fn bench_checksum(b: &mut Bencher) {
         b.iter(|| {
             let address = test::black_box("0xe0fc04fa2d34a66b779fd5cee748268032a146c0");
@@ -213,4 +671,28 @@ mod tests {
             }
         })
     }
+
+    #[bench]
+    fn bench_checksum_encode(b: &mut Bencher) {
+        b.iter(|| {
+            let address = test::black_box("0xe0fc04fa2d34a66b779fd5cee748268032a146c0");
+
+            for _ in 0..20_000 {
+                test::black_box(Checksum::encode(address).unwrap());
+            }
+        })
+    }
+
+    #[bench]
+    fn bench_checksum_encode_to_slice(b: &mut Bencher) {
+        let mut buffer = [0_u8; 42];
+
+        b.iter(|| {
+            let address = test::black_box("0xe0fc04fa2d34a66b779fd5cee748268032a146c0");
+
+            for _ in 0..20_000 {
+                test::black_box(Checksum::encode_to_slice(address, &mut buffer).unwrap());
+            }
+        })
+    }
 }
@@ -0,0 +1,143 @@
+use super::*;
+
+/// An [`Address`] validated at compile time, enabled with the `macros` feature.
+///
+/// Accepts a (prefixed or not) address literal and fails the compilation on an invalid length,
+/// prefix or hex character, as well as on a mixed-case address with an invalid EIP-55 checksum.
+/// The expansion is `const`, so it can be used to define constants:
+///
+/// ```
+/// use eth_checksum::{address, Address};
+///
+/// const ROUTER: Address = address!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+///
+/// assert_eq!(ROUTER, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+/// ```
+///
+/// A typo in a checksummed address doesn't compile:
+///
+/// ```compile_fail
+/// # use eth_checksum::{address, Address};
+/// const ROUTER: Address = address!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD");
+/// ```
+///
+/// Neither does an invalid hex character:
+///
+/// ```compile_fail
+/// # use eth_checksum::{address, Address};
+/// const ROUTER: Address = address!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeq");
+/// ```
+#[macro_export]
+macro_rules! address {
+    ($address:literal) => {{
+        const ADDRESS: $crate::Address = $crate::Address::parse_const($address);
+        ADDRESS
+    }};
+}
+
+impl Address {
+    /// The `const` counterpart of [`Address::parse`], the implementation of the
+    /// [`address!`](crate::address) macro, which isn't part of the public API.
+    ///
+    /// # Panics
+    ///
+    /// On any invalid address, which fails the compilation when evaluated in a `const` context.
+    #[doc(hidden)]
+    pub const fn parse_const(input: &str) -> Self {
+        let input = input.as_bytes();
+        let offset = match input.len() {
+            40 => 0,
+            42 => {
+                if input[0] != b'0' || input[1] != b'x' {
+                    panic!("invalid address prefix, expected `0x`");
+                }
+
+                2
+            }
+            _ => panic!("invalid address length, expected either 40 or 42 characters"),
+        };
+
+        let mut bytes = [0_u8; 20];
+        let mut lowercase = [0_u8; 40];
+        let (mut has_lowercase, mut has_uppercase) = (false, false);
+
+        let mut i = 0;
+        while i < 40 {
+            let byte = input[offset + i];
+            let nibble = match byte {
+                b'0'..=b'9' => byte - b'0',
+                b'a'..=b'f' => {
+                    has_lowercase = true;
+                    byte - b'a' + 10
+                }
+                b'A'..=b'F' => {
+                    has_uppercase = true;
+                    byte - b'A' + 10
+                }
+                _ => panic!("invalid hex character in the address"),
+            };
+
+            lowercase[i] = byte.to_ascii_lowercase();
+            bytes[i / 2] |= if i & 1 == 0 { nibble << 4 } else { nibble };
+            i += 1;
+        }
+
+        if has_lowercase && has_uppercase {
+            let hash = const_keccak::keccak256(&lowercase);
+
+            i = 0;
+            while i < 40 {
+                let half_byte_at = if i & 1 == 0 {
+                    hash[i / 2] >> 4
+                } else {
+                    hash[i / 2] & 0x0f
+                };
+                let byte = input[offset + i];
+
+                if byte.is_ascii_alphabetic() && byte.is_ascii_uppercase() != (half_byte_at >= 8) {
+                    panic!("invalid EIP-55 checksum of the mixed-case address");
+                }
+                i += 1;
+            }
+        }
+
+        Self::new(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::EIP_55_CASES;
+
+    const CHECKSUMMED: Address = address!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+
+    #[test]
+    fn test_address_macro() {
+        assert_eq!(
+            Address::parse("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Ok(CHECKSUMMED)
+        );
+        assert_eq!(
+            CHECKSUMMED,
+            address!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+        );
+        assert_eq!(
+            CHECKSUMMED,
+            address!("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED")
+        );
+    }
+
+    #[test]
+    fn test_parse_const_eip_55_cases() {
+        for address in EIP_55_CASES.iter() {
+            assert_eq!(Address::parse(address), Ok(Address::parse_const(address)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid EIP-55 checksum")]
+    fn test_parse_const_invalid_checksum() {
+        Address::parse_const("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD");
+    }
+}
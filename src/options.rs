@@ -0,0 +1,345 @@
+use super::*;
+
+/// The uppercase prefix accepted with [`ParseOptions::allow_uppercase_prefix`]
+pub(crate) static UPPERCASE_PREFIX: &str = "0X";
+
+/// How the input of a [`Checksummer`] is parsed, see [`Checksum::with_options`].
+///
+/// The default options are strict about the shape of the input (exactly 40 or 42 characters
+/// with a lowercase `0x` prefix), but don't require a checksum:
+///
+/// ```
+/// use eth_checksum::{Checksum, Error, ParseOptions};
+///
+/// let spreadsheet = Checksum::with_options(ParseOptions::lenient().require_prefix(true));
+/// assert_eq!(
+///     Ok("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+///     spreadsheet
+///         .encode(" \"0X5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"\n")
+///         .as_deref()
+/// );
+///
+/// let strict = Checksum::with_options(ParseOptions::new().require_checksum(true));
+/// assert_eq!(
+///     Err(Error::MissingChecksum),
+///     strict.encode("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub(crate) allow_uppercase_prefix: bool,
+    trim_whitespace: bool,
+    strip_quotes: bool,
+    pub(crate) require_prefix: bool,
+    pub(crate) forbid_prefix: bool,
+    require_checksum: bool,
+    reject_all_lowercase: bool,
+    pub(crate) normalize_unicode: bool,
+}
+
+impl ParseOptions {
+    pub const fn new() -> Self {
+        Self {
+            allow_uppercase_prefix: false,
+            trim_whitespace: false,
+            strip_quotes: false,
+            require_prefix: false,
+            forbid_prefix: false,
+            require_checksum: false,
+            reject_all_lowercase: false,
+            normalize_unicode: false,
+        }
+    }
+
+    /// The options for user input, e.g. from spreadsheets: allows an uppercase prefix,
+    /// trims the whitespace and strips the quotes
+    pub const fn lenient() -> Self {
+        Self::new()
+            .allow_uppercase_prefix(true)
+            .trim_whitespace(true)
+            .strip_quotes(true)
+    }
+
+    /// Accepts the `0X` prefix as well
+    pub const fn allow_uppercase_prefix(mut self, allow: bool) -> Self {
+        self.allow_uppercase_prefix = allow;
+        self
+    }
+
+    /// Trims the leading and trailing whitespace, including newlines
+    pub const fn trim_whitespace(mut self, trim: bool) -> Self {
+        self.trim_whitespace = trim;
+        self
+    }
+
+    /// Strips a pair of double or single quotes around the address, after trimming the whitespace
+    pub const fn strip_quotes(mut self, strip: bool) -> Self {
+        self.strip_quotes = strip;
+        self
+    }
+
+    /// Rejects addresses without a prefix with [`Error::Prefix`]
+    pub const fn require_prefix(mut self, require: bool) -> Self {
+        self.require_prefix = require;
+        self
+    }
+
+    /// Rejects prefixed addresses with [`Error::Prefix`], expecting an empty prefix
+    pub const fn forbid_prefix(mut self, forbid: bool) -> Self {
+        self.forbid_prefix = forbid;
+        self
+    }
+
+    /// Requires a valid checksum, i.e. rejects mixed-case addresses with an invalid checksum
+    /// with [`Error::Checksum`] and the all lowercase or all uppercase ones with
+    /// [`Error::MissingChecksum`]. Addresses without any letters can't carry a checksum
+    /// and are accepted.
+    pub const fn require_checksum(mut self, require: bool) -> Self {
+        self.require_checksum = require;
+        self
+    }
+
+    /// Rejects the all lowercase addresses with [`Error::MissingChecksum`],
+    /// e.g. to catch the output of tools that don't checksum at all
+    pub const fn reject_all_lowercase(mut self, reject: bool) -> Self {
+        self.reject_all_lowercase = reject;
+        self
+    }
+
+    /// Maps the Unicode look-alikes of the ASCII characters (e.g. full-width digits or Cyrillic
+    /// letters) to their ASCII equivalents and removes the invisible characters
+    /// (e.g. zero width spaces or direction marks), instead of rejecting them with
+    /// [`Error::Confusable`], see [`unicode`](crate::unicode).
+    ///
+    /// The indices of the errors are still those of the input, invisible characters included.
+    pub const fn normalize_unicode(mut self, normalize: bool) -> Self {
+        self.normalize_unicode = normalize;
+        self
+    }
+
+    /// Trims the input and, with [`ParseOptions::normalize_unicode`], normalizes it
+    /// into a buffer before running `f` on it
+    pub(crate) fn normalized<'a, T>(
+        &self,
+        input: &'a str,
+        f: impl for<'b> FnOnce(&'b str) -> Result<T, Error<'b>>,
+    ) -> Result<T, Error<'a>> {
+        let input = self.trim(input);
+        if !self.normalize_unicode || input.is_ascii() {
+            return f(input);
+        }
+
+        let mut buffer = [0_u8; 42];
+        let normalized =
+            unicode::normalize_into(input, &mut buffer).map_err(|actual| Error::Length {
+                expected_either: [40, 42],
+                actual,
+            })?;
+
+        f(normalized).map_err(|error| error.of_normalized(input, normalized))
+    }
+
+    /// Trims the input as per the options and splits the optional prefix from the address,
+    /// validating the length of the input.
+    ///
+    /// Never slices the input on a non-char boundary, so arbitrary (non-ASCII) input can't panic.
+    pub(crate) fn split_prefix<'a>(
+        &self,
+        input: &'a str,
+    ) -> Result<(Option<&'static str>, &'a str), Error<'a>> {
+        self.split_trimmed_prefix(self.trim(input))
+    }
+
+    /// Same as [`ParseOptions::split_prefix`], for an already trimmed input
+    pub(crate) fn split_trimmed_prefix<'a>(
+        &self,
+        input: &'a str,
+    ) -> Result<(Option<&'static str>, &'a str), Error<'a>> {
+        // the confusables would fail on the length or as hex characters
+        if !input.is_ascii() {
+            if let Some(confusable) = unicode::confusables(input).next() {
+                return Err(Error::Confusable {
+                    value: confusable.value,
+                    index: confusable.index,
+                });
+            }
+        }
+
+        let (prefix, address) = match input.len() {
+            40 => (None, input),
+            42 => match input.strip_prefix(PREFIX) {
+                Some(address) => (Some(PREFIX), address),
+                None => match input.strip_prefix(UPPERCASE_PREFIX) {
+                    Some(address) if self.allow_uppercase_prefix => (Some(PREFIX), address),
+                    _ => return Err(Error::prefix(PREFIX, input)),
+                },
+            },
+            actual => {
+                return Err(Error::Length {
+                    expected_either: [40, 42],
+                    actual,
+                })
+            }
+        };
+
+        match prefix {
+            None if self.require_prefix => Err(Error::prefix(PREFIX, input)),
+            Some(_) if self.forbid_prefix => Err(Error::prefix("", input)),
+            _ => Ok((prefix, address)),
+        }
+    }
+
+    /// Trims the whitespace and strips the quotes as per the options
+    pub fn trim<'a>(&self, mut input: &'a str) -> &'a str {
+        if self.trim_whitespace {
+            input = input.trim();
+        }
+        if self.strip_quotes {
+            input = strip_quotes(input);
+        }
+
+        input
+    }
+
+    /// Checks the verified hex address (without prefix) against the checksum options
+    pub(crate) fn check_checksum<'a>(
+        &self,
+        address: &str,
+        verification: Verification,
+    ) -> Result<(), Error<'a>> {
+        let has_letters = || address.bytes().any(|byte| byte.is_ascii_alphabetic());
+
+        match verification {
+            Verification::Invalid(positions) if self.require_checksum => {
+                Err(Error::Checksum { positions })
+            }
+            Verification::Uppercase if self.require_checksum => Err(Error::MissingChecksum),
+            Verification::Lowercase
+                if (self.require_checksum || self.reject_all_lowercase) && has_letters() =>
+            {
+                Err(Error::MissingChecksum)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn strip_quotes(input: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| input.strip_prefix(*quote)?.strip_suffix(*quote))
+        .unwrap_or(input)
+}
+
+/// The first two characters, which may be more than two bytes
+pub(crate) fn first_two_chars(input: &str) -> &str {
+    let end = input
+        .char_indices()
+        .nth(PREFIX.len())
+        .map_or(input.len(), |(index, _)| index);
+
+    &input[..end]
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::tests::CHECKSUMMED;
+
+    const LOWERCASE: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    #[test]
+    fn test_lenient_input() {
+        let lenient = Checksum::with_options(ParseOptions::lenient());
+
+        let inputs = [
+            "0X5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "  0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\r\n",
+            "\"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"",
+            "\t'0X5aaeb6053f3e94c9b9a09f33669435e7ef1beaed' ",
+        ];
+        for input in inputs.iter() {
+            assert_eq!(Ok(CHECKSUMMED.to_string()), lenient.from_str(input));
+            assert_eq!(Ok(Verification::Lowercase), lenient.verify(input));
+            assert_eq!(
+                Ok(CHECKSUMMED.to_string()),
+                input.try_checksum_with(&lenient)
+            );
+        }
+
+        // only a matching pair of quotes is stripped
+        assert_eq!(
+            Err(Error::Length {
+                expected_either: [40, 42],
+                actual: 44
+            }),
+            lenient.from_str("\"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed'")
+        );
+
+        // the defaults are strict
+        for input in inputs.iter() {
+            assert!(Checksum::from_str(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_prefix_options() {
+        let require = Checksum::with_options(ParseOptions::new().require_prefix(true));
+        assert_eq!(Ok(CHECKSUMMED.to_string()), require.from_str(LOWERCASE));
+        assert_eq!(
+            Err(Error::Prefix {
+                expected: PREFIX,
+                actual: "5a",
+            }),
+            require.from_str(&LOWERCASE[2..])
+        );
+
+        let forbid = Checksum::with_options(ParseOptions::new().forbid_prefix(true));
+        assert_eq!(
+            Ok(CHECKSUMMED[2..].to_string()),
+            forbid.from_str(&LOWERCASE[2..])
+        );
+        let error = forbid.verify(LOWERCASE).unwrap_err();
+        assert_eq!(
+            Error::Prefix {
+                expected: "",
+                actual: "0x",
+            },
+            error
+        );
+        assert_eq!("unexpected address prefix `0x`", error.to_string());
+    }
+
+    #[test]
+    fn test_checksum_options() {
+        let invalid = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        let uppercase = "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED";
+        let digits = "0x1234567890123456789012345678901234567890";
+
+        let require = Checksum::with_options(ParseOptions::new().require_checksum(true));
+        assert_eq!(Ok(CHECKSUMMED.to_string()), require.from_str(CHECKSUMMED));
+        assert_eq!(Ok(digits.to_string()), require.from_str(digits));
+        assert!(matches!(
+            require.verify(invalid),
+            Err(Error::Checksum { .. })
+        ));
+        assert_eq!(Err(Error::MissingChecksum), require.from_str(LOWERCASE));
+        assert_eq!(Err(Error::MissingChecksum), require.encode(uppercase));
+        assert_eq!(
+            Err(Error::MissingChecksum),
+            LOWERCASE.as_bytes().try_verify_with(&require)
+        );
+
+        let no_lowercase = Checksum::with_options(ParseOptions::new().reject_all_lowercase(true));
+        assert_eq!(
+            Err(Error::MissingChecksum),
+            no_lowercase.from_str(LOWERCASE)
+        );
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            no_lowercase.from_str(uppercase)
+        );
+        assert_eq!(Ok(CHECKSUMMED.to_string()), no_lowercase.from_str(invalid));
+        assert_eq!(Ok(Verification::Lowercase), no_lowercase.verify(digits));
+    }
+}
@@ -0,0 +1,524 @@
+//! Rendering an [`Error`] against its input, like a compiler diagnostic.
+use super::*;
+#[cfg(feature = "alloc")]
+use crate::error::OwnedError;
+#[cfg(feature = "miette")]
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use core::{fmt::Write, ops::Range};
+
+impl<'a> Error<'a> {
+    /// Attaches the input the error comes from, to point at the invalid characters.
+    ///
+    /// With the trimming [`ParseOptions`], attach the trimmed input, see [`ParseOptions::trim`].
+    pub fn with_input(self, input: &'a str) -> InputError<'a> {
+        InputError { error: self, input }
+    }
+}
+
+/// An [`Error`] with its input, see [`Error::with_input`].
+///
+/// Displays as the error itself, while [`InputError::render`] points at the invalid characters:
+///
+/// ```
+/// use eth_checksum::Checksum;
+///
+/// let input = "0x5aaeb6053fge94c9b9a09f33669435e7ef1beaed";
+/// let error = Checksum::encode(input).unwrap_err().with_input(input);
+///
+/// assert_eq!(
+///     "error[invalid_hex_char]: invalid hex character 'g' at index 12
+///  | 0x5aaeb6053fge94c9b9a09f33669435e7ef1beaed
+///  |             ^ not a hex character
+///  = help: the hex characters are `0-9`, `a-f` and `A-F`
+/// ",
+///     error.render().to_string()
+/// );
+/// ```
+///
+/// With the `miette` feature it implements [`miette::Diagnostic`], labelling the same characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError<'a> {
+    error: Error<'a>,
+    input: &'a str,
+}
+
+impl<'a> InputError<'a> {
+    pub fn error(&self) -> &Error<'a> {
+        &self.error
+    }
+
+    pub fn input(&self) -> &str {
+        self.input
+    }
+
+    pub fn into_error(self) -> Error<'a> {
+        self.error
+    }
+
+    /// Converts the error into one that no longer borrows from the input
+    #[cfg(feature = "alloc")]
+    pub fn into_owned(self) -> OwnedError {
+        OwnedError::with_input(self.error, self.input)
+    }
+
+    /// Renders the error with the input and carets under the invalid characters
+    pub fn render(&self) -> Rendered<'_, 'a> {
+        Rendered(self)
+    }
+
+    /// The byte ranges of the invalid parts of the input, with their labels
+    fn spans(&self) -> impl Iterator<Item = (Range<usize>, &'static str)> + '_ {
+        let input = self.input();
+        let char_span = |(start, char): (usize, char)| start..start + char.len_utf8();
+
+        let span = match &self.error {
+            // after the invisible chars dropped by `ParseOptions::normalize_unicode`
+            Error::Prefix { expected, actual }
+                if input
+                    .trim_start_matches(unicode::is_invisible)
+                    .starts_with(actual) =>
+            {
+                let label = if expected.is_empty() {
+                    "unexpected prefix"
+                } else {
+                    "expected `0x`"
+                };
+                let start = input.len() - input.trim_start_matches(unicode::is_invisible).len();
+
+                Some((start..start + actual.len(), label))
+            }
+            Error::HexChar { value, index } => input
+                .char_indices()
+                .nth(*index)
+                .filter(|(_, char)| char == value)
+                .map(|indexed| (char_span(indexed), "not a hex character")),
+            Error::Confusable { value, index } => input
+                .char_indices()
+                .nth(*index)
+                .filter(|(_, char)| char == value)
+                .map(|indexed| {
+                    let label = if unicode::is_invisible(*value) {
+                        "invisible character"
+                    } else {
+                        "not an ASCII character"
+                    };
+
+                    (char_span(indexed), label)
+                }),
+            _ => None,
+        };
+
+        // the nibbles, i.e. without the invisible chars dropped by `ParseOptions::normalize_unicode`
+        let nibbles = move || {
+            input
+                .char_indices()
+                .filter(|(_, value)| !unicode::is_invisible(*value))
+        };
+        let (offset, positions) = match self.error {
+            Error::Checksum { positions } if nibbles().count() == 42 => (PREFIX.len(), positions),
+            Error::Checksum { positions } => (0, positions),
+            _ => (0, Positions::default()),
+        };
+        let wrongly_cased = nibbles()
+            .skip(offset)
+            .enumerate()
+            .filter(move |(position, _)| positions.contains(*position))
+            .map(move |(_, indexed)| (char_span(indexed), "wrongly cased"));
+
+        span.into_iter().chain(wrongly_cased)
+    }
+
+    fn help(&self) -> Help<'_, 'a> {
+        Help {
+            error: &self.error,
+            input: Some(self.input()),
+        }
+    }
+
+    /// Whether the input is relevant for the error, i.e. is echoed back
+    fn shows_input(&self) -> bool {
+        !matches!(self.error, Error::Utf8(_) | Error::ByteLength { .. })
+    }
+}
+
+impl fmt::Display for InputError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InputError<'_> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+/// The help of an error, more specific with its input
+struct Help<'e, 'a> {
+    error: &'e Error<'a>,
+    input: Option<&'e str>,
+}
+
+impl Help<'_, '_> {
+    fn is_some(&self) -> bool {
+        !matches!(self.error, Error::Utf8(_))
+    }
+}
+
+impl fmt::Display for Help<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Error::Length { .. } | Error::Prefix { .. }, Some(input)) = (self.error, self.input)
+        {
+            if let Some(result) = surrounding_help(f, input) {
+                return result;
+            }
+        }
+
+        match (self.error, self.input) {
+            (Error::Length { actual, .. }, Some(input)) => length_help(f, *actual, input),
+            (Error::Length { .. }, None) => {
+                f.write_str("an address has 40 hex characters, optionally prefixed with `0x`")
+            }
+            (Error::Prefix { expected: "", .. }, _) => f.write_str("remove the `0x` prefix"),
+            (Error::Prefix { actual, .. }, _)
+                if unicode::normalized_chars(actual).eq("0X".chars()) =>
+            {
+                f.write_str(
+                    "the uppercase prefix is accepted with `ParseOptions::allow_uppercase_prefix`",
+                )
+            }
+            (Error::Prefix { .. }, _) => f.write_str("prefix the address with `0x`"),
+            (Error::HexChar { value, .. }, _) => match hex::confusable_digit(*value) {
+                Some(b'0') => f.write_str("did you mean `0` (zero)?"),
+                Some(b'1') => f.write_str("did you mean `1` (one)?"),
+                Some(digit) => write!(f, "did you mean `{}`?", char::from(digit)),
+                None => f.write_str("the hex characters are `0-9`, `a-f` and `A-F`"),
+            },
+            (Error::Confusable { value, .. }, _) => {
+                match unicode::Confusable::new(*value, 0).and_then(|confusable| confusable.ascii) {
+                    Some(ascii) => write!(f, "replace it with `{}`", ascii)?,
+                    None => f.write_str("remove it")?,
+                }
+                f.write_str(", or use `ParseOptions::normalize_unicode`")
+            }
+            (Error::Checksum { .. }, _) => f.write_str(
+                "the checksum catches typos, double check the address \
+                 (or its chain id, for EIP-1191 checksums)",
+            ),
+            (Error::MissingChecksum, _) => {
+                f.write_str("the address can't be checked for typos, use its checksummed form")
+            }
+            (Error::ByteLength { .. }, _) => f.write_str("a raw address has 20 bytes"),
+            (Error::Utf8(_), _) => Ok(()),
+        }
+    }
+}
+
+/// The whitespace and quotes are common in pasted addresses
+fn surrounding_help(f: &mut fmt::Formatter<'_>, input: &str) -> Option<fmt::Result> {
+    if input.trim() != input {
+        Some(f.write_str(
+            "the input includes whitespace, trim it or use `ParseOptions::trim_whitespace`",
+        ))
+    } else if input.starts_with(['"', '\''].as_ref()) {
+        Some(
+            f.write_str(
+                "the input includes quotes, strip them or use `ParseOptions::strip_quotes`",
+            ),
+        )
+    } else {
+        None
+    }
+}
+
+fn length_help(f: &mut fmt::Formatter<'_>, actual: usize, input: &str) -> fmt::Result {
+    let prefixed = input.starts_with(PREFIX) || input.starts_with("0X");
+    let hex_length = if prefixed {
+        actual.saturating_sub(PREFIX.len())
+    } else {
+        actual
+    };
+
+    match hex_length {
+        0 => f.write_str("the address is empty"),
+        42 if prefixed && input[PREFIX.len()..].starts_with(PREFIX) => {
+            write!(f, "{} bytes: a doubled `0x` prefix?", actual)
+        }
+        1..=39 => write!(
+            f,
+            "{} bytes: {} hex character(s) missing",
+            actual,
+            40 - hex_length
+        ),
+        41 if !prefixed => write!(
+            f,
+            "{} bytes: one extra hex character, or a mistyped `0x` prefix?",
+            actual
+        ),
+        _ => write!(
+            f,
+            "{} bytes: {} extra hex character(s)",
+            actual,
+            hex_length.saturating_sub(40)
+        ),
+    }
+}
+
+/// The rendering of an [`InputError`], see [`InputError::render`]
+pub struct Rendered<'e, 'a>(&'e InputError<'a>);
+
+impl fmt::Display for Rendered<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let InputError { error, input } = self.0;
+        writeln!(f, "error[{}]: {}", error.code(), error)?;
+
+        if self.0.shows_input() {
+            // one column per char, so the carets line up
+            f.write_str(" | ")?;
+            for char in input.chars() {
+                f.write_char(if char.is_control() || unicode::is_invisible(char) {
+                    '\u{fffd}'
+                } else {
+                    char
+                })?;
+            }
+            writeln!(f)?;
+
+            let mut spans = self.0.spans().peekable();
+            if spans.peek().is_some() {
+                f.write_str(" | ")?;
+
+                let (mut column, mut last_label) = (0, "");
+                for (span, label) in spans {
+                    let start = input[..span.start].chars().count();
+                    let width = input[span].chars().count();
+
+                    write!(f, "{:1$}", "", start - column)?;
+                    for _ in 0..width {
+                        f.write_char('^')?;
+                    }
+                    column = start + width;
+                    last_label = label;
+                }
+
+                writeln!(f, " {}", last_label)?;
+            }
+        }
+
+        let help = self.0.help();
+        if help.is_some() {
+            writeln!(f, " = help: {}", help)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Error<'_> {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(Error::code(self)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Help {
+            error: self,
+            input: None,
+        })
+        .filter(Help::is_some)
+        .map(|help| Box::new(help) as Box<dyn fmt::Display + 'a>)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for InputError<'_> {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.error.code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(self.help())
+            .filter(Help::is_some)
+            .map(|help| Box::new(help) as Box<dyn fmt::Display + 'a>)
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        if self.shows_input() {
+            Some(&self.input)
+        } else {
+            None
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let labels = self
+            .spans()
+            .map(|(span, label)| miette::LabeledSpan::at(span, label));
+
+        Some(Box::new(labels))
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for OwnedError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(OwnedError::code(self)))
+    }
+
+    // the help and labels are those of the borrowing error, rendered or collected
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let error = self.error();
+        Some(Help {
+            error: &error,
+            input: self.input(),
+        })
+        .filter(Help::is_some)
+        .map(|help| Box::new(help.to_string()) as Box<dyn fmt::Display + 'a>)
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        match (&self.input, self.error()) {
+            (_, Error::Utf8(_) | Error::ByteLength { .. }) => None,
+            (Some(input), _) => Some(input),
+            (None, _) => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let input = self.input()?;
+        let labels = self
+            .error()
+            .with_input(input)
+            .spans()
+            .map(|(span, label)| miette::LabeledSpan::at(span, label))
+            .collect::<Vec<_>>();
+
+        Some(Box::new(labels.into_iter()))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn render(input: &str) -> String {
+        Checksum::validate(input)
+            .expect_err("Should be an invalid address")
+            .with_input(input)
+            .render()
+            .to_string()
+    }
+
+    #[test]
+    fn test_render_prefix_and_length() {
+        assert_eq!(
+            "error[invalid_prefix]: invalid address prefix `0X`, expected `0x`\n \
+             | 0X5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\n \
+             | ^^ expected `0x`\n \
+             = help: the uppercase prefix is accepted with `ParseOptions::allow_uppercase_prefix`\n",
+            render("0X5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+        );
+
+        assert_eq!(
+            "error[invalid_length]: invalid address length of 41 bytes, \
+             expected either 40 or 42 (with prefix)\n \
+             | 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beae\n \
+             = help: 41 bytes: 1 hex character(s) missing\n",
+            render("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beae")
+        );
+
+        let hints = [
+            (
+                "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed0",
+                "one extra hex character",
+            ),
+            (
+                "0x0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "a doubled `0x` prefix?",
+            ),
+            (
+                " 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "includes whitespace",
+            ),
+            (
+                "'5aaeb6053f3e94c9b9a09f33669435e7ef1beaed'",
+                "includes quotes",
+            ),
+            ("", "the address is empty"),
+        ];
+        for (input, hint) in hints.iter() {
+            assert!(render(input).contains(hint), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_render_checksum() {
+        // `A` at index 2 and `e` at index 39 are wrongly cased
+        assert_eq!(
+            "error[invalid_checksum]: invalid checksum, wrongly cased characters at {2, 39}\n \
+             | 0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD\n \
+             |     ^                                    ^ wrongly cased\n \
+             = help: the checksum catches typos, double check the address \
+             (or its chain id, for EIP-1191 checksums)\n",
+            render("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD")
+        );
+    }
+
+    #[test]
+    fn test_render_non_ascii_input() {
+        // the caret is under the multi-byte char, in the unprefixed address
+        let input = "5aaeb6053f3e94c9b9a09f33669435e7ef1bea\u{e9}";
+        assert!(render(input).contains(&format!(" | {}^ not a hex character", " ".repeat(38))));
+
+        // the same invalid char in the unprefixed address and after where a prefix would end
+        assert_eq!(
+            "error[invalid_hex_char]: invalid hex character 'g' at index 0\n \
+             | g0g0000000000000000000000000000000000000\n \
+             | ^ not a hex character\n \
+             = help: the hex characters are `0-9`, `a-f` and `A-F`\n",
+            render("g0g0000000000000000000000000000000000000")
+        );
+        let prefixed = "0xg0g0000000000000000000000000000000000000";
+        assert!(render(prefixed).contains("\n |   ^ not a hex character\n"));
+
+        let error = Error::HexChar {
+            value: 'q',
+            index: 100,
+        };
+        assert_eq!(0, error.with_input(input).spans().count());
+    }
+
+    #[test]
+    #[cfg(feature = "miette")]
+    fn test_miette_diagnostic() {
+        use miette::Diagnostic;
+
+        let input = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeo";
+        let error = Checksum::from_str(input)
+            .unwrap_err()
+            .with_input(input)
+            .into_owned();
+
+        assert_eq!(
+            Some("invalid_hex_char".to_string()),
+            Diagnostic::code(&error).map(|code| code.to_string())
+        );
+        assert_eq!(
+            Some("did you mean `0` (zero)?".to_string()),
+            Diagnostic::help(&error).map(|help| help.to_string())
+        );
+        assert!(error.source_code().is_some());
+
+        let labels = error
+            .labels()
+            .expect("Should have labels")
+            .collect::<Vec<_>>();
+        assert_eq!(1, labels.len());
+        assert_eq!(41, labels[0].offset());
+        assert_eq!(Some("not a hex character"), labels[0].label());
+
+        let report = miette::Report::new(error);
+        assert_eq!("invalid hex character 'o' at index 41", report.to_string());
+    }
+}
@@ -0,0 +1,227 @@
+//! Serde support for [`Address`], enabled with the `serde` feature.
+//!
+//! By default an [`Address`] is serialized as a checksummed string and deserialized strictly,
+//! i.e. a mixed-case address must have a valid checksum.
+//! Use the modules with `#[serde(with = "...")]` to select a different format:
+//!
+//! - [`checksummed`] - emit checksummed, deserialize strictly (same as the default)
+//! - [`lowercase`] - emit lowercase, deserialize leniently
+//! - [`bytes`] - emit the 20 raw bytes for binary formats
+//! - [`strict`] - emit checksummed, reject mixed-case addresses with an invalid checksum
+//! - [`lenient`] - emit checksummed, accept any casing
+use crate::{Address, Error};
+use ::serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use alloc::format;
+use core::{convert::TryFrom, fmt};
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        checksummed::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        strict::deserialize(deserializer)
+    }
+}
+
+/// Maps the crate [`Error`] to the closest serde error
+fn to_de_error<E: de::Error>(error: Error<'_>) -> E {
+    match error {
+        Error::Length { actual, .. } => {
+            E::invalid_length(actual, &"an address of 40 or 42 characters")
+        }
+        Error::Prefix { actual, .. } => {
+            E::invalid_value(Unexpected::Str(actual), &"the `0x` prefix")
+        }
+        error @ Error::Utf8(_) => E::custom(error),
+        Error::HexChar { value, index } => E::invalid_value(
+            Unexpected::Char(value),
+            &format!("a hex character at index {}", index).as_str(),
+        ),
+        error @ Error::Confusable { .. } => E::custom(error),
+        error @ Error::Checksum { .. } => E::custom(error),
+        error @ Error::MissingChecksum => E::custom(error),
+        Error::ByteLength { actual, .. } => E::invalid_length(actual, &"an address of 20 bytes"),
+    }
+}
+
+struct AddressVisitor {
+    strict: bool,
+}
+
+impl<'de> Visitor<'de> for AddressVisitor {
+    type Value = Address;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an Ethereum address")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        let address = if self.strict {
+            Address::parse(value)
+        } else {
+            Address::parse_lenient(value)
+        };
+
+        address.map_err(to_de_error)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Address::try_from(value).map_err(to_de_error)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0_u8; 20];
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &"an address of 20 bytes"))?;
+        }
+
+        match seq.next_element::<u8>()? {
+            Some(_) => Err(de::Error::invalid_length(21, &"an address of 20 bytes")),
+            None => Ok(Address::from(bytes)),
+        }
+    }
+}
+
+/// Serializes as a checksummed string and deserializes strictly
+pub mod checksummed {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(address)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        strict::deserialize(deserializer)
+    }
+}
+
+/// Serializes as a lowercase prefixed string and deserializes leniently
+pub mod lowercase {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:#x}", address))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        lenient::deserialize(deserializer)
+    }
+}
+
+/// Serializes as the 20 raw bytes of the address, meant for binary formats
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(address.as_bytes())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        deserializer.deserialize_bytes(AddressVisitor { strict: true })
+    }
+}
+
+/// Serializes as a checksummed string and rejects mixed-case addresses with an invalid checksum
+pub mod strict {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        checksummed::serialize(address, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        deserializer.deserialize_str(AddressVisitor { strict: true })
+    }
+}
+
+/// Serializes as a checksummed string and accepts addresses of any casing
+pub mod lenient {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        checksummed::serialize(address, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        deserializer.deserialize_str(AddressVisitor { strict: false })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::CHECKSUMMED;
+    use ::serde::{Deserialize, Serialize};
+    const INVALID_CHECKSUM: &str = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Formats {
+        default: Address,
+        #[serde(with = "crate::serde::lowercase")]
+        lowercase: Address,
+        #[serde(with = "crate::serde::bytes")]
+        bytes: Address,
+    }
+
+    #[test]
+    fn test_serialize_and_deserialize_formats() {
+        let address = Address::parse(CHECKSUMMED).expect("Should be valid address");
+        let formats = Formats {
+            default: address,
+            lowercase: address,
+            bytes: address,
+        };
+
+        let json = serde_json::to_value(&formats).expect("Should serialize");
+        assert_eq!(CHECKSUMMED, json["default"]);
+        assert_eq!(
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            json["lowercase"]
+        );
+        assert_eq!(
+            20,
+            json["bytes"].as_array().map(Vec::len).unwrap_or_default()
+        );
+
+        assert_eq!(
+            formats,
+            serde_json::from_value(json).expect("Should deserialize")
+        );
+    }
+
+    #[test]
+    fn test_strict_and_lenient_deserialization() {
+        #[derive(Debug, Deserialize)]
+        struct Lenient {
+            #[serde(with = "crate::serde::lenient")]
+            address: Address,
+        }
+
+        let json = format!(r#"{{ "address": "{}" }}"#, INVALID_CHECKSUM);
+
+        let lenient: Lenient = serde_json::from_str(&json).expect("Should ignore the casing");
+        assert_eq!(CHECKSUMMED, lenient.address.to_string());
+
+        let strict_err = serde_json::from_str::<Address>(&format!(r#""{}""#, INVALID_CHECKSUM))
+            .expect_err("Should reject the invalid checksum");
+        assert!(strict_err
+            .to_string()
+            .starts_with("invalid checksum, wrongly cased characters at {2, 39}"));
+
+        let hex_char_err =
+            serde_json::from_str::<Address>(r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeq""#)
+                .expect_err("Should reject the invalid hex character");
+        assert!(hex_char_err
+            .to_string()
+            .starts_with("invalid value: character `q`, expected a hex character at index 41"));
+    }
+}
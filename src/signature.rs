@@ -0,0 +1,326 @@
+//! Recovering the signer address of a secp256k1 signature, i.e. `ecrecover`,
+//! enabled with the `k256` feature.
+use super::*;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+/// The EIP-191 prefix of the `personal_sign` messages, followed by the message length
+const MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// An invalid signature, or the signature of another address, see [`Address::recover`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// Invalid length of a signature, in bytes, which must be the 65 bytes of `r`, `s` and `v`
+    SignatureLength { actual: usize },
+    /// Invalid recovery id `v` of a signature, which must be 27 or 28, or 0 or 1
+    RecoveryId { actual: u8 },
+    /// A signature from which no public key can be recovered
+    Signature,
+    /// A valid signature of another address than the `expected` one,
+    /// see [`verify_signer`]
+    Signer {
+        expected: Address,
+        recovered: Address,
+    },
+}
+
+impl SignatureError {
+    /// A stable, machine-readable code of the error, see [`Error::code`]
+    pub fn code(&self) -> &'static str {
+        match self {
+            SignatureError::SignatureLength { .. } => "invalid_signature_length",
+            SignatureError::RecoveryId { .. } => "invalid_recovery_id",
+            SignatureError::Signature => "invalid_signature",
+            SignatureError::Signer { .. } => "signer_mismatch",
+        }
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::SignatureLength { actual } => write!(
+                f,
+                "invalid signature length of {} bytes, expected 65",
+                actual
+            ),
+            SignatureError::RecoveryId { actual } => write!(
+                f,
+                "invalid signature recovery id {}, expected 27, 28, 0 or 1",
+                actual
+            ),
+            SignatureError::Signature => {
+                f.write_str("invalid signature, no public key can be recovered")
+            }
+            SignatureError::Signer {
+                expected,
+                recovered,
+            } => write!(f, "signature of {}, expected {}", recovered, expected),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignatureError {}
+
+/// The error of [`verify_signer`], either of the expected address or of the signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerError<'a> {
+    Address(Error<'a>),
+    Signature(SignatureError),
+}
+
+impl SignerError<'_> {
+    /// A stable, machine-readable code of the error, see [`Error::code`]
+    pub fn code(&self) -> &'static str {
+        match self {
+            SignerError::Address(error) => error.code(),
+            SignerError::Signature(error) => error.code(),
+        }
+    }
+}
+
+impl fmt::Display for SignerError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerError::Address(error) => fmt::Display::fmt(error, f),
+            SignerError::Signature(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignerError<'_> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SignerError::Address(error) => std::error::Error::source(error),
+            SignerError::Signature(error) => std::error::Error::source(error),
+        }
+    }
+}
+
+impl<'a> From<Error<'a>> for SignerError<'a> {
+    fn from(error: Error<'a>) -> Self {
+        Self::Address(error)
+    }
+}
+
+impl From<SignatureError> for SignerError<'_> {
+    fn from(error: SignatureError) -> Self {
+        Self::Signature(error)
+    }
+}
+
+/// The EIP-191 hash of a `personal_sign` message, i.e. the Keccak-256 hash of the
+/// message prefixed with `"\x19Ethereum Signed Message:\n"` and its length in decimal
+pub fn hash_message(message: &[u8]) -> [u8; 32] {
+    let mut buffer = [0_u8; 20];
+
+    let mut hasher = Keccak256Hasher::new();
+    hasher.update(MESSAGE_PREFIX);
+    hasher.update(decimal(message.len() as u64, &mut buffer));
+    hasher.update(message);
+
+    hasher.finalize()
+}
+
+/// Verifies that the EIP-191 `signature` of the `message` is one of the `address`,
+/// returning the checksummed address.
+///
+/// The `address` is compared regardless of its casing, and a mismatch is a
+/// [`SignatureError::Signer`] holding both addresses, which are displayed checksummed.
+///
+/// ```
+/// use eth_checksum::{verify_signer, SignatureError, SignerError};
+///
+/// let signature = [
+///     0xb9, 0x14, 0x67, 0xe5, 0x70, 0xa6, 0x46, 0x6a, 0xa9, 0xe9, 0x87, 0x6c, 0xbc, 0xd0, 0x13,
+///     0xba, 0xba, 0x02, 0x90, 0x0b, 0x89, 0x79, 0xd4, 0x3f, 0xe2, 0x08, 0xa4, 0xa4, 0xf3, 0x39,
+///     0xf5, 0xfd, 0x60, 0x07, 0xe7, 0x4c, 0xd8, 0x2e, 0x03, 0x7b, 0x80, 0x01, 0x86, 0x42, 0x2f,
+///     0xc2, 0xda, 0x16, 0x7c, 0x74, 0x7e, 0xf0, 0x45, 0xe5, 0xd1, 0x8a, 0x5f, 0x5d, 0x43, 0x00,
+///     0xf8, 0xe1, 0xa0, 0x29, 0x1c,
+/// ];
+///
+/// let signer = verify_signer(
+///     "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+///     b"Some data",
+///     &signature,
+/// );
+/// assert_eq!("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23", &*signer.unwrap());
+///
+/// let error = verify_signer(
+///     "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+///     b"Some data",
+///     &signature,
+/// )
+/// .unwrap_err();
+/// assert!(matches!(
+///     error,
+///     SignerError::Signature(SignatureError::Signer { .. })
+/// ));
+/// ```
+pub fn verify_signer<'a>(
+    address: &'a str,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<ChecksummedAddress, SignerError<'a>> {
+    let expected = Address::parse_lenient(address)?;
+    let recovered = Address::recover_message(message, signature)?;
+
+    if recovered == expected {
+        Ok(recovered.checksummed())
+    } else {
+        Err(SignatureError::Signer {
+            expected,
+            recovered,
+        }
+        .into())
+    }
+}
+
+impl Address {
+    /// Recovers the signer address of a 65 bytes `r ‖ s ‖ v` signature of a 32 bytes digest.
+    ///
+    /// Like the `ecrecover` precompile, the recovery id `v` is either 27 or 28 (0 or 1 are
+    /// accepted too) and a high `s` is accepted.
+    pub fn recover(digest: &[u8; 32], signature: &[u8]) -> Result<Self, SignatureError> {
+        let (rs, v) = match signature {
+            [rs @ .., v] if rs.len() == 64 => (rs, *v),
+            _ => {
+                return Err(SignatureError::SignatureLength {
+                    actual: signature.len(),
+                })
+            }
+        };
+        let is_y_odd = match v {
+            0 | 27 => false,
+            1 | 28 => true,
+            _ => return Err(SignatureError::RecoveryId { actual: v }),
+        };
+
+        let signature = Signature::from_slice(rs).map_err(|_| SignatureError::Signature)?;
+        // `(r, -s)` is the signature of the opposite point `-R`
+        let (signature, is_y_odd) = match signature.normalize_s() {
+            Some(normalized) => (normalized, !is_y_odd),
+            None => (signature, is_y_odd),
+        };
+
+        VerifyingKey::recover_from_prehash(digest, &signature, RecoveryId::new(is_y_odd, false))
+            .map(|verifying_key| Self::from(k256::PublicKey::from(&verifying_key)))
+            .map_err(|_| SignatureError::Signature)
+    }
+
+    /// Recovers the signer address of an EIP-191 (`personal_sign`) signature of the `message`,
+    /// see [`Address::recover`]
+    pub fn recover_message(message: &[u8], signature: &[u8]) -> Result<Self, SignatureError> {
+        Self::recover(&hash_message(message), signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::hex_bytes;
+
+    const SIGNER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+    fn signature() -> Vec<u8> {
+        hex_bytes(
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+             6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029\
+             1c",
+        )
+    }
+
+    #[test]
+    fn test_recover_message() {
+        let private_key =
+            hex_bytes("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let address = Address::from_private_key(&private_key[..].try_into().unwrap()).unwrap();
+        assert_eq!(SIGNER, &*address.checksummed());
+
+        let digest = hash_message(b"Some data");
+        assert_eq!(
+            hex_bytes("1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655"),
+            digest
+        );
+        assert_eq!(Ok(address), Address::recover(&digest, &signature()));
+        assert_eq!(
+            Ok(address),
+            Address::recover_message(b"Some data", &signature())
+        );
+
+        // `v` as 0 or 1
+        let mut signature = signature();
+        signature[64] -= 27;
+        assert_eq!(Ok(address), Address::recover(&digest, &signature));
+
+        // the high `s` of the same signature, i.e. `n - s` and the other `v`
+        let order = hex_bytes("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let mut borrow = 0;
+        for i in (0..32).rev() {
+            let difference = i16::from(order[i]) - i16::from(signature[32 + i]) - borrow;
+            signature[32 + i] = difference.rem_euclid(256) as u8;
+            borrow = i16::from(difference < 0);
+        }
+        signature[64] = 1 - signature[64];
+        assert_eq!(Ok(address), Address::recover(&digest, &signature));
+
+        // another message
+        assert_ne!(
+            Ok(address),
+            Address::recover_message(b"Some date", &signature)
+        );
+    }
+
+    #[test]
+    fn test_verify_signer() {
+        let uppercase = SIGNER.to_uppercase();
+        let signer = verify_signer(&uppercase[2..], b"Some data", &signature());
+        assert_eq!(Ok(SIGNER), signer.as_deref());
+
+        let error = verify_signer(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            b"Some data",
+            &signature(),
+        )
+        .unwrap_err();
+        assert_eq!("signer_mismatch", error.code());
+        assert_eq!(
+            "signature of 0x2c7536E3605D9C16a7a3D7b1898e529396a65c23, \
+             expected 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            error.to_string()
+        );
+
+        // the invalid address is reported first
+        assert!(matches!(
+            verify_signer("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe", b"", &[]),
+            Err(SignerError::Address(Error::Length { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_signatures() {
+        let digest = hash_message(b"Some data");
+        assert_eq!(
+            Err(SignatureError::SignatureLength { actual: 64 }),
+            Address::recover(&digest, &signature()[..64])
+        );
+
+        let mut signature = signature();
+        signature[64] = 29;
+        let error = Address::recover(&digest, &signature).unwrap_err();
+        assert_eq!(SignatureError::RecoveryId { actual: 29 }, error);
+        assert_eq!(
+            "invalid signature recovery id 29, expected 27, 28, 0 or 1",
+            error.to_string()
+        );
+
+        // a zero `r`
+        signature[..32].copy_from_slice(&[0; 32]);
+        signature[64] = 27;
+        assert_eq!(
+            Err(SignatureError::Signature),
+            Address::recover(&digest, &signature)
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Suggesting the intended address of a mistyped one, using the checksum as an error-detecting code.
+//!
+//! Every letter of a checksummed address carries a bit of its hash in its casing, so a candidate
+//! correction whose checksum matches the casing of the untouched letters is very likely the
+//! intended address.
+use super::*;
+use crate::address::HEX_CHARS;
+use alloc::vec::Vec;
+
+/// A candidate correction of a mistyped address, see [`Checksummer::suggest_corrections`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Correction {
+    /// The corrected and checksummed address
+    pub address: ChecksummedAddress,
+    /// The number of edits, a transposition of adjacent characters being a single edit
+    pub distance: usize,
+    /// The corrected positions in the hex part of the address, i.e. without the prefix
+    pub positions: Positions,
+}
+
+impl Checksum {
+    /// Suggests corrections of a mistyped (prefixed or not) address,
+    /// see [`Checksummer::suggest_corrections`].
+    pub fn suggest_corrections(input: &str) -> Result<Vec<Correction>, Error<'_>> {
+        <Checksummer>::default().suggest_corrections(input)
+    }
+}
+
+impl<H: Keccak256> Checksummer<H> {
+    /// Suggests corrections of a mixed-case address with an invalid checksum or invalid hex
+    /// characters, ranked by their edit distance.
+    ///
+    /// The candidates are the single hex character substitutions, the transpositions
+    /// of adjacent characters and the common OCR confusions (`O` → `0`, `l` → `1`, `I` → `1`,
+    /// `S` → `5`), and only those whose checksum matches the casing of the input are kept.
+    /// An address without a checksum has its confusions corrected, but nothing else,
+    /// as any edit would match.
+    ///
+    /// A valid address has no corrections, while an input which can't be split into
+    /// the prefix and the 40 characters of the address is an error.
+    ///
+    /// ```
+    /// use eth_checksum::Checksum;
+    ///
+    /// // the `6` of `b6053` is mistyped as an `8`
+    /// let corrections =
+    ///     Checksum::suggest_corrections("0x5aAeb8053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap();
+    ///
+    /// assert_eq!(
+    ///     "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    ///     &*corrections[0].address
+    /// );
+    /// assert_eq!(1, corrections[0].distance);
+    /// ```
+    pub fn suggest_corrections<'a>(&self, input: &'a str) -> Result<Vec<Correction>, Error<'a>> {
+        self.options
+            .normalized(input, |input| self.suggest_trimmed(input))
+    }
+
+    fn suggest_trimmed<'a>(&self, input: &'a str) -> Result<Vec<Correction>, Error<'a>> {
+        let (prefix, address) = self.options.split_trimmed_prefix(input)?;
+        // the 40 bytes are 40 characters
+        if let Some(index) = address.bytes().position(|byte| !byte.is_ascii()) {
+            return Err(invalid_hex_char(address, index).after_prefix(prefix));
+        }
+
+        let mut typed = [0_u8; 40];
+        let mut confused = Positions::default();
+        let mut unknown = None;
+        for (i, (byte, typed)) in address.bytes().zip(typed.iter_mut()).enumerate() {
+            *typed = match hex::confusable_digit(char::from(byte)) {
+                _ if byte.is_ascii_hexdigit() => byte,
+                Some(digit) => {
+                    confused.insert(i);
+                    digit
+                }
+                // can only be fixed by a substitution, of which there's a single one
+                None if unknown.is_none() => {
+                    unknown = Some(i);
+                    b'0'
+                }
+                None => return Ok(Vec::new()),
+            };
+        }
+
+        let letters = || address.bytes().filter(|byte| byte.is_ascii_hexdigit());
+        let has_checksum = letters().any(|byte| byte.is_ascii_lowercase())
+            && letters().any(|byte| byte.is_ascii_uppercase());
+
+        let mut corrections = Vec::new();
+        let mut suggest = |hex: [u8; 40], positions: Positions, distance: usize| {
+            if let Some(address) = self.checksum_candidate(&typed, hex, positions, has_checksum) {
+                corrections.push(Correction {
+                    address,
+                    distance,
+                    positions,
+                });
+            }
+        };
+
+        match unknown {
+            Some(index) if has_checksum => {
+                let mut positions = confused;
+                positions.insert(index);
+
+                for &hex_char in HEX_CHARS.iter() {
+                    let mut hex = typed;
+                    hex[index] = hex_char;
+                    suggest(hex, positions, confused.len() + 1);
+                }
+            }
+            Some(_) => {}
+            None if !has_checksum => {
+                if !confused.is_empty() {
+                    suggest(typed, confused, confused.len());
+                }
+            }
+            None => {
+                // the confusions alone, or an already valid address
+                if !confused.is_empty() {
+                    suggest(typed, confused, confused.len());
+                } else if self
+                    .checksum_candidate(&typed, typed, confused, has_checksum)
+                    .is_some()
+                {
+                    return Ok(Vec::new());
+                }
+
+                for i in 0..typed.len() - 1 {
+                    if !typed[i].eq_ignore_ascii_case(&typed[i + 1]) {
+                        let mut hex = typed;
+                        hex.swap(i, i + 1);
+
+                        let mut positions = confused;
+                        positions.insert(i);
+                        positions.insert(i + 1);
+                        suggest(hex, positions, confused.len() + 1);
+                    }
+                }
+
+                for i in 0..typed.len() {
+                    for &hex_char in HEX_CHARS.iter() {
+                        if !typed[i].eq_ignore_ascii_case(&hex_char) {
+                            let mut hex = typed;
+                            hex[i] = hex_char;
+
+                            let mut positions = confused;
+                            positions.insert(i);
+                            suggest(hex, positions, positions.len());
+                        }
+                    }
+                }
+            }
+        }
+
+        // stable, i.e. the confusions alone come first and then the transpositions
+        corrections.sort_by_key(|correction| correction.distance);
+
+        Ok(corrections)
+    }
+
+    /// Checksums the hex candidate, if its checksum matches the typed casing outside
+    /// of the corrected positions
+    fn checksum_candidate(
+        &self,
+        typed: &[u8; 40],
+        hex: [u8; 40],
+        positions: Positions,
+        has_checksum: bool,
+    ) -> Option<ChecksummedAddress> {
+        let mut buffer = [0_u8; 42];
+        buffer[..2].copy_from_slice(PREFIX.as_bytes());
+
+        let checksummed: &mut [u8; 40] = (&mut buffer[2..])
+            .try_into()
+            .expect("Buffer has 40 bytes after the prefix");
+        hex::to_lowercase(&hex, checksummed).ok()?;
+        apply_checksum::<H>(checksummed, self.chain_id);
+
+        let matches = typed
+            .iter()
+            .zip(checksummed.iter())
+            .enumerate()
+            .all(|(i, (typed, checksummed))| positions.contains(i) || typed == checksummed);
+
+        if matches || !has_checksum {
+            Some(ChecksummedAddress::from_buffer(buffer))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::CHECKSUMMED;
+
+    fn suggested(input: &str) -> Vec<(String, usize)> {
+        Checksum::suggest_corrections(input)
+            .expect("Should be an address")
+            .into_iter()
+            .map(|correction| (correction.address.to_string(), correction.distance))
+            .collect()
+    }
+
+    #[test]
+    fn test_suggest_substitutions_and_transpositions() {
+        let substituted = "0x5aAeb8053F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert_eq!(
+            Some(&(CHECKSUMMED.to_string(), 1)),
+            suggested(substituted).first()
+        );
+
+        let transposed = "0x5aAeb6503F3E94C9b9A09f33669435E7Ef1BeAed";
+        let corrections = Checksum::suggest_corrections(transposed).unwrap();
+        assert_eq!(CHECKSUMMED, &*corrections[0].address);
+        assert_eq!(
+            vec![6, 7],
+            corrections[0].positions.iter().collect::<Vec<_>>()
+        );
+
+        // a non hex character can only be substituted
+        let unknown = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg";
+        assert_eq!(
+            vec![(CHECKSUMMED.to_string(), 1)],
+            suggested(unknown)
+                .into_iter()
+                .filter(|(address, _)| address == CHECKSUMMED)
+                .collect::<Vec<_>>()
+        );
+        assert!(suggested("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAgg").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_confusions() {
+        // `0` as `O`, `5` as `S` and a transposition
+        let confused = "0x5aAeb6O53F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert_eq!(
+            Some(&(CHECKSUMMED.to_string(), 1)),
+            suggested(confused).first()
+        );
+        let corrections = suggested("0xSaAeb6O35F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(Some(&(CHECKSUMMED.to_string(), 3)), corrections.first());
+
+        // no checksum to match, so only the confusions are corrected
+        assert_eq!(
+            vec![(CHECKSUMMED.to_string(), 2)],
+            suggested("0x5aaeb6o53f3e94c9b9a09f3366943Se7ef1beaed")
+        );
+    }
+
+    #[test]
+    fn test_suggest_nothing() {
+        assert!(suggested(CHECKSUMMED).is_empty());
+        assert!(suggested(&CHECKSUMMED.to_lowercase()).is_empty());
+        assert!(suggested(&CHECKSUMMED.to_uppercase()[2..]).is_empty());
+
+        assert_eq!(
+            Err(Error::Length {
+                expected_either: [40, 42],
+                actual: 41,
+            }),
+            Checksum::suggest_corrections(&CHECKSUMMED[..41])
+        );
+        assert!(matches!(
+            Checksum::suggest_corrections("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1Be\u{00e9}d"),
+            Err(Error::HexChar { index: 39, .. })
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+use super::*;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
+/// Checksums and verifies the common shapes of an address.
+///
+/// Strings and ASCII bytes hold a (prefixed or not) hex address, while the raw `[u8; 20]`
+/// and [`Address`] are checksummed directly, bypassing any hex validation.
+///
+/// The `_with` methods checksum with the given [`Checksummer`], i.e. with its chain id
+/// and [`ParseOptions`]. The raw bytes carry no input to parse and only use the chain id.
+///
+/// A blanket implementation for `AsRef<str>` would conflict with the byte implementations,
+/// so every string type is implemented separately.
+pub trait TryChecksum {
+    #[cfg(feature = "alloc")]
+    fn try_checksum(&self) -> Result<String, Error<'_>> {
+        self.try_checksum_with(&Checksummer::<DefaultKeccak>::default())
+    }
+
+    fn try_verify(&self) -> Result<Verification, Error<'_>> {
+        self.try_verify_with(&Checksummer::<DefaultKeccak>::default())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn try_checksum_with<'a, H: Keccak256>(
+        &'a self,
+        checksummer: &Checksummer<H>,
+    ) -> Result<String, Error<'a>>;
+
+    fn try_verify_with<'a, H: Keccak256>(
+        &'a self,
+        checksummer: &Checksummer<H>,
+    ) -> Result<Verification, Error<'a>>;
+}
+
+/// Implements `TryChecksum` for the types dereferencing to a `str`
+macro_rules! impl_str {
+    ($($ty:ty),+) => {
+        $(
+            impl TryChecksum for $ty {
+                #[cfg(feature = "alloc")]
+                fn try_checksum_with<'a, H: Keccak256>(
+                    &'a self,
+                    checksummer: &Checksummer<H>,
+                ) -> Result<String, Error<'a>> {
+                    checksummer.from_str(self)
+                }
+
+                fn try_verify_with<'a, H: Keccak256>(
+                    &'a self,
+                    checksummer: &Checksummer<H>,
+                ) -> Result<Verification, Error<'a>> {
+                    checksummer.verify(self)
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `TryChecksum` for the types dereferencing to ASCII bytes
+macro_rules! impl_ascii_bytes {
+    ($($ty:ty),+) => {
+        $(
+            impl TryChecksum for $ty {
+                #[cfg(feature = "alloc")]
+                fn try_checksum_with<'a, H: Keccak256>(
+                    &'a self,
+                    checksummer: &Checksummer<H>,
+                ) -> Result<String, Error<'a>> {
+                    let string = core::str::from_utf8(self)?;
+                    checksummer.from_str(string)
+                }
+
+                fn try_verify_with<'a, H: Keccak256>(
+                    &'a self,
+                    checksummer: &Checksummer<H>,
+                ) -> Result<Verification, Error<'a>> {
+                    let string = core::str::from_utf8(self)?;
+                    checksummer.verify(string)
+                }
+            }
+        )+
+    };
+}
+
+impl_str!(str);
+#[cfg(feature = "alloc")]
+impl_str!(String, Box<str>, Cow<'_, str>);
+
+impl_ascii_bytes!([u8], [u8; 40], [u8; 42]);
+#[cfg(feature = "alloc")]
+impl_ascii_bytes!(Vec<u8>);
+
+/// The raw bytes carry no casing, so they are verified as an address without a checksum,
+/// i.e. [`Verification::Lowercase`].
+impl TryChecksum for [u8; 20] {
+    #[cfg(feature = "alloc")]
+    fn try_checksum_with<'a, H: Keccak256>(
+        &'a self,
+        checksummer: &Checksummer<H>,
+    ) -> Result<String, Error<'a>> {
+        Ok(checksummer.encode_address(&Address::from(*self)).into())
+    }
+
+    fn try_verify_with<'a, H: Keccak256>(
+        &'a self,
+        _: &Checksummer<H>,
+    ) -> Result<Verification, Error<'a>> {
+        Ok(Verification::Lowercase)
+    }
+}
+
+/// See the raw `[u8; 20]` implementation
+impl TryChecksum for Address {
+    #[cfg(feature = "alloc")]
+    fn try_checksum_with<'a, H: Keccak256>(
+        &'a self,
+        checksummer: &Checksummer<H>,
+    ) -> Result<String, Error<'a>> {
+        Ok(checksummer.encode_address(self).into())
+    }
+
+    fn try_verify_with<'a, H: Keccak256>(
+        &'a self,
+        _: &Checksummer<H>,
+    ) -> Result<Verification, Error<'a>> {
+        Ok(Verification::Lowercase)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{keccak::PortableKeccak, tests::CHECKSUMMED};
+
+    #[test]
+    fn test_try_checksum_string_and_ascii_shapes() {
+        let lowercase = CHECKSUMMED.to_lowercase();
+
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            lowercase.as_str().try_checksum()
+        );
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            lowercase.clone().into_boxed_str().try_checksum()
+        );
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            Cow::Borrowed(lowercase.as_str()).try_checksum()
+        );
+
+        let mut prefixed = [0_u8; 42];
+        prefixed.copy_from_slice(lowercase.as_bytes());
+        assert_eq!(Ok(CHECKSUMMED.to_string()), prefixed.try_checksum());
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            lowercase.as_bytes().try_checksum()
+        );
+        assert_eq!(
+            Ok(Verification::Valid),
+            CHECKSUMMED.as_bytes().to_vec().try_verify()
+        );
+
+        assert!(matches!(
+            [0xff_u8; 40][..].try_checksum(),
+            Err(Error::Utf8(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_checksum_with_hasher() {
+        let portable = Checksum::with_hasher::<PortableKeccak>();
+
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            CHECKSUMMED.to_lowercase().try_checksum_with(&portable)
+        );
+        assert_eq!(
+            Ok(Verification::Valid),
+            CHECKSUMMED.as_bytes().try_verify_with(&portable)
+        );
+    }
+
+    #[test]
+    fn test_try_checksum_raw_bytes() {
+        let raw = *Address::parse(CHECKSUMMED)
+            .expect("Should be valid address")
+            .as_bytes();
+
+        assert_eq!(Ok(CHECKSUMMED.to_string()), raw.try_checksum());
+        assert_eq!(Ok(Verification::Lowercase), raw.try_verify());
+        assert_eq!(
+            Ok(CHECKSUMMED.to_string()),
+            Address::from(raw).try_checksum()
+        );
+    }
+}
@@ -0,0 +1,395 @@
+//! The Unicode look-alikes of the ASCII characters of an address and the invisible characters,
+//! which are common in addresses pasted from chat apps and documents.
+//!
+//! They are reported with [`Error::Confusable`], or mapped to their ASCII equivalents
+//! (and removed, for the invisible ones) with
+//! [`ParseOptions::normalize_unicode`](crate::ParseOptions::normalize_unicode).
+//!
+//! ```
+//! use eth_checksum::unicode;
+//!
+//! // a full-width `０ｘ` prefix and a zero width space
+//! let input = "０ｘ5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\u{200b}";
+//!
+//! let names = unicode::confusables(input)
+//!     .map(|confusable| (confusable.index, confusable.name))
+//!     .collect::<Vec<_>>();
+//! assert_eq!(
+//!     vec![
+//!         (0, "FULLWIDTH DIGIT ZERO"),
+//!         (1, "FULLWIDTH LATIN SMALL LETTER X"),
+//!         (42, "ZERO WIDTH SPACE"),
+//!     ],
+//!     names
+//! );
+//! ```
+use crate::options::first_two_chars;
+use crate::Error;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+/// The confusable characters with their Unicode name and ASCII equivalent, `None` for the
+/// invisible ones, sorted by the character
+static CONFUSABLES: [(char, &str, Option<char>); 58] = [
+    ('\u{00ad}', "SOFT HYPHEN", None),
+    ('\u{0391}', "GREEK CAPITAL LETTER ALPHA", Some('A')),
+    ('\u{0392}', "GREEK CAPITAL LETTER BETA", Some('B')),
+    ('\u{0395}', "GREEK CAPITAL LETTER EPSILON", Some('E')),
+    ('\u{03a7}', "GREEK CAPITAL LETTER CHI", Some('X')),
+    ('\u{0410}', "CYRILLIC CAPITAL LETTER A", Some('A')),
+    ('\u{0412}', "CYRILLIC CAPITAL LETTER VE", Some('B')),
+    ('\u{0415}', "CYRILLIC CAPITAL LETTER IE", Some('E')),
+    ('\u{0417}', "CYRILLIC CAPITAL LETTER ZE", Some('3')),
+    ('\u{0421}', "CYRILLIC CAPITAL LETTER ES", Some('C')),
+    ('\u{0425}', "CYRILLIC CAPITAL LETTER HA", Some('X')),
+    ('\u{0430}', "CYRILLIC SMALL LETTER A", Some('a')),
+    ('\u{0435}', "CYRILLIC SMALL LETTER IE", Some('e')),
+    ('\u{0441}', "CYRILLIC SMALL LETTER ES", Some('c')),
+    ('\u{0445}', "CYRILLIC SMALL LETTER HA", Some('x')),
+    ('\u{0501}', "CYRILLIC SMALL LETTER KOMI DE", Some('d')),
+    ('\u{061c}', "ARABIC LETTER MARK", None),
+    ('\u{180e}', "MONGOLIAN VOWEL SEPARATOR", None),
+    ('\u{200b}', "ZERO WIDTH SPACE", None),
+    ('\u{200c}', "ZERO WIDTH NON-JOINER", None),
+    ('\u{200d}', "ZERO WIDTH JOINER", None),
+    ('\u{200e}', "LEFT-TO-RIGHT MARK", None),
+    ('\u{200f}', "RIGHT-TO-LEFT MARK", None),
+    ('\u{202a}', "LEFT-TO-RIGHT EMBEDDING", None),
+    ('\u{202b}', "RIGHT-TO-LEFT EMBEDDING", None),
+    ('\u{202c}', "POP DIRECTIONAL FORMATTING", None),
+    ('\u{202d}', "LEFT-TO-RIGHT OVERRIDE", None),
+    ('\u{202e}', "RIGHT-TO-LEFT OVERRIDE", None),
+    ('\u{2060}', "WORD JOINER", None),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE", None),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE", None),
+    ('\u{2068}', "FIRST STRONG ISOLATE", None),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE", None),
+    ('\u{feff}', "ZERO WIDTH NO-BREAK SPACE", None),
+    ('\u{ff10}', "FULLWIDTH DIGIT ZERO", Some('0')),
+    ('\u{ff11}', "FULLWIDTH DIGIT ONE", Some('1')),
+    ('\u{ff12}', "FULLWIDTH DIGIT TWO", Some('2')),
+    ('\u{ff13}', "FULLWIDTH DIGIT THREE", Some('3')),
+    ('\u{ff14}', "FULLWIDTH DIGIT FOUR", Some('4')),
+    ('\u{ff15}', "FULLWIDTH DIGIT FIVE", Some('5')),
+    ('\u{ff16}', "FULLWIDTH DIGIT SIX", Some('6')),
+    ('\u{ff17}', "FULLWIDTH DIGIT SEVEN", Some('7')),
+    ('\u{ff18}', "FULLWIDTH DIGIT EIGHT", Some('8')),
+    ('\u{ff19}', "FULLWIDTH DIGIT NINE", Some('9')),
+    ('\u{ff21}', "FULLWIDTH LATIN CAPITAL LETTER A", Some('A')),
+    ('\u{ff22}', "FULLWIDTH LATIN CAPITAL LETTER B", Some('B')),
+    ('\u{ff23}', "FULLWIDTH LATIN CAPITAL LETTER C", Some('C')),
+    ('\u{ff24}', "FULLWIDTH LATIN CAPITAL LETTER D", Some('D')),
+    ('\u{ff25}', "FULLWIDTH LATIN CAPITAL LETTER E", Some('E')),
+    ('\u{ff26}', "FULLWIDTH LATIN CAPITAL LETTER F", Some('F')),
+    ('\u{ff38}', "FULLWIDTH LATIN CAPITAL LETTER X", Some('X')),
+    ('\u{ff41}', "FULLWIDTH LATIN SMALL LETTER A", Some('a')),
+    ('\u{ff42}', "FULLWIDTH LATIN SMALL LETTER B", Some('b')),
+    ('\u{ff43}', "FULLWIDTH LATIN SMALL LETTER C", Some('c')),
+    ('\u{ff44}', "FULLWIDTH LATIN SMALL LETTER D", Some('d')),
+    ('\u{ff45}', "FULLWIDTH LATIN SMALL LETTER E", Some('e')),
+    ('\u{ff46}', "FULLWIDTH LATIN SMALL LETTER F", Some('f')),
+    ('\u{ff58}', "FULLWIDTH LATIN SMALL LETTER X", Some('x')),
+];
+
+/// A Unicode look-alike of an ASCII character, or an invisible character, see [`confusables`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confusable {
+    pub value: char,
+    /// The char index in the input
+    pub index: usize,
+    /// The Unicode name of the character
+    pub name: &'static str,
+    /// The ASCII equivalent, `None` for the invisible characters
+    pub ascii: Option<char>,
+}
+
+impl Confusable {
+    /// The confusable character at the char `index`, if it is one
+    pub(crate) fn new(value: char, index: usize) -> Option<Self> {
+        if value.is_ascii() {
+            return None;
+        }
+
+        CONFUSABLES
+            .binary_search_by_key(&value, |(confusable, _, _)| *confusable)
+            .ok()
+            .map(|i| {
+                let (_, name, ascii) = CONFUSABLES[i];
+
+                Self {
+                    value,
+                    index,
+                    name,
+                    ascii,
+                }
+            })
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        self.ascii.is_none()
+    }
+}
+
+/// Whether the character is one of the invisible ones, e.g. a zero width space
+pub(crate) fn is_invisible(value: char) -> bool {
+    Confusable::new(value, 0).is_some_and(|confusable| confusable.is_invisible())
+}
+
+/// The confusable and invisible characters of the input, in order
+pub fn confusables(input: &str) -> impl Iterator<Item = Confusable> + '_ {
+    input
+        .chars()
+        .enumerate()
+        .filter(|(_, value)| !value.is_ascii())
+        .filter_map(|(index, value)| Confusable::new(value, index))
+}
+
+/// Maps the confusable characters to their ASCII equivalents and removes the invisible ones,
+/// see [`ParseOptions::normalize_unicode`](crate::ParseOptions::normalize_unicode).
+///
+/// The other (non-ASCII) characters are kept as they are.
+///
+/// ```
+/// use eth_checksum::unicode;
+///
+/// // a full-width `０ｘ` prefix and a zero width space
+/// let input = "０ｘ5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\u{200b}";
+/// assert_eq!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", unicode::normalize(input));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    if confusables(input).next().is_none() {
+        return Cow::Borrowed(input);
+    }
+
+    Cow::Owned(normalized_chars(input).collect::<String>())
+}
+
+/// Normalizes the input into the buffer without allocating, or returns the byte length
+/// of the normalized input when it doesn't fit, see [`normalize`]
+pub(crate) fn normalize_into<'b>(input: &str, buffer: &'b mut [u8]) -> Result<&'b str, usize> {
+    let mut length = 0;
+    for value in normalized_chars(input) {
+        if let Some(bytes) = buffer.get_mut(length..length + value.len_utf8()) {
+            value.encode_utf8(bytes);
+        }
+        length += value.len_utf8();
+    }
+
+    match buffer.get(..length) {
+        Some(normalized) => Ok(core::str::from_utf8(normalized).expect("Whole chars are written")),
+        None => Err(length),
+    }
+}
+
+pub(crate) fn normalized_chars(input: &str) -> impl Iterator<Item = char> + '_ {
+    input
+        .chars()
+        .filter_map(|value| match Confusable::new(value, 0) {
+            Some(confusable) => confusable.ascii,
+            None => Some(value),
+        })
+}
+
+impl Error<'_> {
+    /// Moves the error of the `normalized` input to the trimmed `input` it was normalized from,
+    /// i.e. maps the index of an [`Error::HexChar`] back to the input and borrows the prefix
+    /// of the input.
+    ///
+    /// The positions of an [`Error::Checksum`] are those of the nibbles, whatever the input.
+    pub(crate) fn of_normalized<'i>(self, input: &'i str, normalized: &str) -> Error<'i> {
+        // the visible chars of the input are those of the normalized input
+        let visible = || {
+            input
+                .char_indices()
+                .enumerate()
+                .filter(|(_, (_, value))| !is_invisible(*value))
+        };
+
+        let error = self.map_prefix(|_| {
+            let first_two = first_two_chars(normalized);
+            let (start, end) = visible()
+                .take(first_two.chars().count())
+                .fold(None, |range, (_, (start, value))| {
+                    Some((
+                        range.map_or(start, |(start, _)| start),
+                        start + value.len_utf8(),
+                    ))
+                })
+                .unwrap_or((0, 0));
+
+            &input[start..end]
+        });
+
+        match error {
+            Error::HexChar { value, index } => Error::HexChar {
+                value,
+                index: visible().nth(index).map_or(index, |(index, _)| index),
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::{tests::CHECKSUMMED, Checksum, ParseOptions};
+
+    #[test]
+    fn test_confusables_table_is_sorted() {
+        assert!(CONFUSABLES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(CONFUSABLES.iter().all(|(value, _, ascii)| !value.is_ascii()
+            && ascii.is_none_or(|ascii| ascii.is_ascii_alphanumeric())));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_confusable_errors() {
+        // Cyrillic `а` and `е`, which look exactly like the ASCII ones
+        let cyrillic = "0x5\u{0430}Aeb6053F3E94C9b9A09f33669435E7Ef1B\u{0435}Aed";
+        let error = Checksum::from_str(cyrillic).unwrap_err();
+        assert_eq!(
+            Error::Confusable {
+                value: '\u{0430}',
+                index: 3,
+            },
+            error
+        );
+        assert_eq!("confusable_char", error.code());
+        assert_eq!(
+            "confusable character 'а' (CYRILLIC SMALL LETTER A) at index 3, looks like 'a'",
+            error.to_string()
+        );
+
+        let invisible =
+            Checksum::verify("0x5aAeb6053F3E94C9b9A09\u{200f}f33669435E7Ef1BeAed").unwrap_err();
+        assert_eq!(
+            "invisible character '\\u{200f}' (RIGHT-TO-LEFT MARK) at index 23",
+            invisible.to_string()
+        );
+
+        // every confusable is reported by the diagnostics, the other characters as usual
+        assert_eq!(
+            vec![
+                Error::Confusable {
+                    value: '\u{0430}',
+                    index: 3,
+                },
+                Error::Confusable {
+                    value: '\u{0435}',
+                    index: 38,
+                },
+            ],
+            Checksum::diagnose(cyrillic)
+        );
+        assert_eq!(
+            vec![
+                Error::Confusable {
+                    value: '\u{ff10}',
+                    index: 0,
+                },
+                Error::HexChar {
+                    value: '\u{e9}',
+                    index: 41,
+                },
+            ],
+            Checksum::diagnose("\u{ff10}x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe\u{e9}")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_normalized_errors_point_at_the_input() {
+        let normalizing = Checksum::with_options(ParseOptions::new().normalize_unicode(true));
+        let render = |input: &str| {
+            normalizing
+                .validate(input)
+                .unwrap_err()
+                .with_input(input)
+                .render()
+                .to_string()
+        };
+
+        let uppercase_prefix = "\u{200b}0X5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let error = normalizing.validate(uppercase_prefix).unwrap_err();
+        assert_eq!(
+            Error::Prefix {
+                expected: "0x",
+                actual: "0X",
+            },
+            error
+        );
+        assert!(matches!(
+            error,
+            Error::Prefix { actual, .. } if core::ptr::eq(actual.as_ptr(), uppercase_prefix[3..].as_ptr())
+        ));
+        let rendered = render(uppercase_prefix);
+        assert!(
+            rendered.contains("\n |  ^^ expected `0x`\n"),
+            "{}",
+            rendered
+        );
+        assert!(rendered.contains("`ParseOptions::allow_uppercase_prefix`"));
+        assert!(
+            render("\u{ff10}\u{ff38}5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+                .contains("`ParseOptions::allow_uppercase_prefix`")
+        );
+
+        // the index counts the invisible chars of the input
+        let hex_char = "0x5aAeb\u{200b}6053F3E94C9b9A09f33669435E7Ef1BeAeg";
+        assert_eq!(
+            Err(Error::HexChar {
+                value: 'g',
+                index: 42,
+            }),
+            normalizing.validate(hex_char)
+        );
+        assert!(render(hex_char).contains(&format!("\n | {}^ not a hex character", " ".repeat(42))));
+
+        let checksum = "\u{feff}0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        let rendered = render(checksum);
+        assert!(
+            rendered.contains("\n |      ^                                    ^ wrongly cased"),
+            "{}",
+            rendered
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_normalize_unicode() {
+        let normalizing = Checksum::with_options(ParseOptions::new().normalize_unicode(true));
+
+        let inputs = [
+            "\u{ff10}\u{ff58}5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "\u{feff}0x5\u{0430}Aeb6053F3E94C9b9A09f33669435E7Ef1B\u{0435}Aed\u{200b}",
+            "0x\u{202e}\u{ff15}\u{ff41}\u{ff21}\u{ff45}\u{ff42}\u{ff16}\u{ff10}\u{ff15}\u{ff13}\
+             F3E94C9b9A09f33669435E7Ef1BeAed\u{202c}",
+        ];
+        for input in inputs.iter() {
+            assert_eq!(Ok(CHECKSUMMED.to_string()), normalizing.from_str(input));
+            assert!(normalizing.diagnose(input).is_empty());
+            assert!(Checksum::from_str(input).is_err());
+            assert_eq!(CHECKSUMMED.to_lowercase(), normalize(input).to_lowercase());
+        }
+        assert!(matches!(normalize(CHECKSUMMED), Cow::Borrowed(_)));
+
+        // the errors are those of the normalized input, mapped back to the input
+        assert_eq!(
+            Err(Error::Prefix {
+                expected: "0x",
+                actual: "\u{ff10}\u{ff38}",
+            }),
+            normalizing.verify("\u{200b}\u{ff10}\u{ff38}5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+        );
+        assert_eq!(
+            Err(Error::Length {
+                expected_either: [40, 42],
+                actual: 43,
+            }),
+            normalizing.encode("0x5\u{0430}Aeb6053F3E94C9b9A09f33669435E7Ef1BeAed0")
+        );
+    }
+}